@@ -2,36 +2,123 @@
 mod tools;
 use std::env;
 
-use tools::table::Table;
+use tools::initial::Registry;
+use tools::table::{Balancing, Objective, Table};
 
 fn main() {
     env::set_var("RUST_BACKTRACE", "1");
 
-    let mut table: Table<u32> = Table::from_file("data/7.txt");
-    table.display(&table.costs());
+    // usage: transporteur [file] [initializer] [--balance[=cost]] [--maximize] [--flow] [--network-simplex] [--bottleneck] [--ranging] [--shadow-prices]
+    let (options, args): (Vec<String>, Vec<String>) =
+        env::args().skip(1).partition(|arg| arg.starts_with("--"));
+    let filename = args.first().cloned().unwrap_or("data/7.txt".to_string());
+    let name = args
+        .get(1)
+        .cloned()
+        .unwrap_or("north-west-corner".to_string());
+    let balancing = match options
+        .iter()
+        .find_map(|option| option.strip_prefix("--balance"))
+    {
+        Some("") => Balancing::Dummy(0),
+        Some(cost) => match cost.strip_prefix('=').and_then(|cost| cost.parse().ok()) {
+            Some(cost) => Balancing::Dummy(cost),
+            None => {
+                eprintln!("Invalid balancing cost: --balance{}", cost);
+                std::process::exit(1);
+            }
+        },
+        None => Balancing::Reject,
+    };
 
-    table.north_west_corner();
+    let registry: Registry<u32> = Registry::default();
+    let heuristic = match registry.get(&name) {
+        Some(heuristic) => heuristic,
+        None => {
+            eprintln!(
+                "Unknown initializer: {} (available: {})",
+                name,
+                registry.names().join(", ")
+            );
+            std::process::exit(1);
+        }
+    };
 
-    table.display(&table.transport());
+    let mut table: Table<u32> = match Table::from_file_with(&filename, balancing) {
+        Ok(table) => table,
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    };
+    if options.iter().any(|option| option == "--maximize") {
+        table.set_objective(Objective::Maximize);
+    }
+    table.display(table.costs());
 
-    let mut graph = table.get_graph();
+    // the costs are read as times, the longest one used is minimized
+    if options.iter().any(|option| option == "--bottleneck") {
+        match table.bottleneck::<i64>(true) {
+            Ok(bottleneck) => table.report_bottleneck(&bottleneck),
+            Err(error) => {
+                eprintln!("{}", error);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
 
-    graph
-        .k_edge_augmentation(1, table.get_unused_edges())
-        .unwrap();
+    // compare the initial cost of every heuristic
+    for heuristic in registry.iter() {
+        println!(
+            "Initial cost ({}) : {}",
+            heuristic.name(),
+            table.initialize_with(heuristic)
+        );
+    }
 
-    println!("{:?}", graph);
-    println!("{:?}", graph.is_tree());
+    println!("Initial cost : {}", table.initialize_with(heuristic));
+    table.display(table.transport());
 
-    println!("Potentials : {:?}", table.potentials::<i64>(&graph));
-
-    let matrix = table.marginal_cost::<i64>(&graph);
-    let min_marginal_cost = matrix.min().unwrap();
-
-    println!("Marginal cost : {:?}", matrix);
-    println!("Min marginal cost : {:?}", min_marginal_cost);
-    println!(
-        "Index of min marginal cost : {:?}",
-        matrix.index_of(min_marginal_cost).unwrap()
-    );
+    // square problems with unit supplies and demands go to the Hungarian algorithm
+    let network_simplex = options.iter().any(|option| option == "--network-simplex");
+    let solution = if table.is_assignment() {
+        println!("Assignment problem, solved with the Hungarian algorithm");
+        table.assign::<i64>()
+    } else if network_simplex {
+        table.network_simplex::<i64>()
+    } else {
+        table.optimize::<i64>()
+    };
+    match solution {
+        Ok(solution) => {
+            table.report(&solution);
+            // cross-check against the min-cost flow backend
+            if options.iter().any(|option| option == "--flow") {
+                let flow = table.min_cost_flow::<i64>().unwrap();
+                println!(
+                    "Min-cost flow : {} ({})",
+                    flow.total_cost,
+                    if flow.total_cost == solution.total_cost {
+                        "matches"
+                    } else {
+                        "differs"
+                    }
+                );
+            }
+            // the network simplex leaves the basis of the table untouched
+            if options.iter().any(|option| option == "--ranging") && !network_simplex {
+                let ranges = table.cost_ranging::<i64>(&table.get_graph());
+                table.report_cost_ranging(&ranges);
+            }
+            if options.iter().any(|option| option == "--shadow-prices") && !network_simplex {
+                let prices = table.shadow_prices::<i64>(&table.get_graph());
+                table.report_shadow_prices(&prices);
+            }
+        }
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    }
 }
@@ -0,0 +1,343 @@
+// several commodities shipped between the same sources and destinations, one table each,
+// sharing the capacity of every lane. solved by Lagrangian decomposition: the joint
+// capacities are priced into the costs with one multiplier per lane, every commodity is
+// then solved on its own with MODI and the multipliers follow the excess flow on each lane
+// (subgradient steps). there is no LP backend in the crate, decomposition is the only method
+
+use std::fmt::Display;
+use tabled::{
+    builder::Builder,
+    settings::{Alignment, Style},
+};
+
+use crate::tools::error::SolveError;
+use crate::tools::matrix::Matrix;
+use crate::tools::number::{Number, Signed};
+use crate::tools::table::{Objective, Solution, Table};
+
+pub struct MultiCommodity<T> {
+    names: Vec<String>,
+    tables: Vec<Table<T>>,
+    // joint capacity of each lane over all the commodities, None when unbounded
+    capacity: Matrix<Option<T>>,
+    max_iterations: usize,
+}
+
+pub struct CommodityPlan<T> {
+    pub name: String,
+    pub transport: Matrix<T>,
+    pub cost: T,
+}
+
+pub struct MultiCommoditySolution<T, V> {
+    pub plans: Vec<CommodityPlan<T>>,
+    // flow of all the commodities on each lane
+    pub flow: Matrix<T>,
+    pub total_cost: T,
+    // best value of the Lagrangian relaxation, the optimum is between it and total_cost
+    pub lower_bound: V,
+    pub iterations: usize,
+}
+
+impl<T> MultiCommodity<T>
+where
+    T: Number,
+{
+    pub fn new(n: usize, m: usize) -> Self {
+        Self {
+            names: Vec::new(),
+            tables: Vec::new(),
+            capacity: Matrix::new_empty(n, m),
+            max_iterations: 100,
+        }
+    }
+
+    pub fn add_commodity(&mut self, name: &str, table: Table<T>) {
+        assert_eq!(
+            (table.costs().rows(), table.costs().cols()),
+            (self.capacity.rows(), self.capacity.cols()),
+            "Every commodity must use the same sources and destinations"
+        );
+        assert_eq!(
+            table.objective(),
+            Objective::Minimize,
+            "The commodities are shipped at minimum cost"
+        );
+        self.names.push(name.to_string());
+        self.tables.push(table);
+    }
+
+    pub fn set_capacity(&mut self, (i, j): (usize, usize), capacity: Option<T>) {
+        self.capacity[(i, j)] = capacity;
+    }
+
+    pub fn capacity(&self) -> &Matrix<Option<T>> {
+        &self.capacity
+    }
+
+    pub fn set_max_iterations(&mut self, max_iterations: usize) {
+        self.max_iterations = max_iterations;
+    }
+
+    pub fn solve<V>(&self) -> Result<MultiCommoditySolution<T, V>, SolveError>
+    where
+        V: Signed,
+        T: Into<V>,
+    {
+        let (n, m) = (self.capacity.rows(), self.capacity.cols());
+        let lanes: Vec<(usize, usize)> = (0..n)
+            .flat_map(|i| (0..m).map(move |j| (i, j)))
+            .filter(|&cell| self.capacity[cell].is_some())
+            .collect();
+
+        // the step starts at the largest cost and shrinks as 1/k, it never goes below 1
+        let first_step = self
+            .tables
+            .iter()
+            .flat_map(|table| table.costs().data().iter().flatten().copied())
+            .max()
+            .unwrap_or_default()
+            .max(1.into());
+        let mut round: T = 1.into();
+
+        let mut penalty: Matrix<T> = Matrix::new_empty(n, m);
+        // the costs are non-negative, so zero is always a lower bound
+        let mut lower_bound = V::default();
+        let mut best: Option<(T, Vec<Matrix<T>>)> = None;
+        let mut iterations = 0;
+        while iterations < self.max_iterations {
+            iterations += 1;
+
+            // relaxed problem: every commodity alone, the lanes priced by the penalties
+            let mut plans = Vec::new();
+            let mut relaxed = V::default();
+            for table in self.tables.iter() {
+                let solution = Self::solve_commodity::<V>(table, &penalty, None)?;
+                relaxed = relaxed + solution.total_cost.into();
+                plans.push(solution.transport);
+            }
+            for &cell in lanes.iter() {
+                let capacity = self.capacity[cell].unwrap();
+                relaxed -= penalty[cell].into() * capacity.into();
+            }
+            lower_bound = lower_bound.max(relaxed);
+
+            // the relaxed plans are kept when they fit the lanes, otherwise the commodities
+            // are routed one after the other on what the previous ones left
+            let flow = Self::aggregate(&plans, n, m);
+            let fits = lanes
+                .iter()
+                .all(|&cell| flow[cell] <= self.capacity[cell].unwrap());
+            let candidate = if fits {
+                Some(plans)
+            } else {
+                self.repair::<V>(&penalty)
+            };
+            if let Some(candidate) = candidate {
+                let cost = self.cost_of(&candidate);
+                if best.as_ref().is_none_or(|(min, _)| cost < *min) {
+                    best = Some((cost, candidate));
+                }
+            }
+            if best
+                .as_ref()
+                .is_some_and(|(cost, _)| (*cost).into() == lower_bound)
+            {
+                break;
+            }
+
+            // subgradient step: overloaded lanes get more expensive, the penalty of a lane
+            // with spare capacity goes back down towards zero
+            let step = (first_step / round).max(1.into());
+            round += 1.into();
+            let mut moved = false;
+            for &cell in lanes.iter() {
+                let capacity = self.capacity[cell].unwrap();
+                if flow[cell] > capacity {
+                    penalty[cell] += step * (flow[cell] - capacity);
+                    moved = true;
+                } else if penalty[cell] != Default::default() {
+                    let decrease = penalty[cell].min(step * (capacity - flow[cell]));
+                    penalty[cell] -= decrease;
+                    moved |= decrease != Default::default();
+                }
+            }
+            if !moved {
+                break;
+            }
+        }
+
+        let (total_cost, transports) = best.ok_or(SolveError::NoFeasiblePlan { iterations })?;
+        let flow = Self::aggregate(&transports, n, m);
+        let plans = transports
+            .into_iter()
+            .zip(self.tables.iter().zip(self.names.iter()))
+            .map(|(transport, (table, name))| CommodityPlan {
+                name: name.clone(),
+                cost: table.cost_of(&transport),
+                transport,
+            })
+            .collect();
+        Ok(MultiCommoditySolution {
+            plans,
+            flow,
+            total_cost,
+            lower_bound,
+            iterations,
+        })
+    }
+
+    fn solve_commodity<V>(
+        table: &Table<T>,
+        penalty: &Matrix<T>,
+        residual: Option<&Matrix<Option<T>>>,
+    ) -> Result<Solution<T>, SolveError>
+    where
+        V: Signed,
+        T: Into<V>,
+    {
+        // the commodity with the penalties added to its costs and, when given, its own
+        // capacities cut down to what is left of the lanes
+        let mut table = table.clone();
+        let mut costs = table.costs().clone();
+        for i in 0..costs.rows() {
+            for j in 0..costs.cols() {
+                costs[(i, j)] += penalty[(i, j)];
+                if let Some(left) = residual.and_then(|residual| residual[(i, j)]) {
+                    let capacity = table.capacity()[(i, j)].map_or(left, |own| own.min(left));
+                    table.set_capacity((i, j), Some(capacity));
+                }
+            }
+        }
+        table.set_costs(costs);
+        table.russell();
+        table.optimize::<V>()
+    }
+
+    fn repair<V>(&self, penalty: &Matrix<T>) -> Option<Vec<Matrix<T>>>
+    where
+        V: Signed,
+        T: Into<V>,
+    {
+        let mut residual = self.capacity.clone();
+        let mut plans = Vec::new();
+        for table in self.tables.iter() {
+            let solution = Self::solve_commodity::<V>(table, penalty, Some(&residual)).ok()?;
+            for i in 0..residual.rows() {
+                for j in 0..residual.cols() {
+                    if let Some(left) = residual[(i, j)].as_mut() {
+                        *left -= solution.transport[(i, j)];
+                    }
+                }
+            }
+            plans.push(solution.transport);
+        }
+        Some(plans)
+    }
+
+    fn aggregate(plans: &[Matrix<T>], n: usize, m: usize) -> Matrix<T> {
+        let mut flow = Matrix::new_empty(n, m);
+        for plan in plans.iter() {
+            for i in 0..n {
+                for j in 0..m {
+                    flow[(i, j)] += plan[(i, j)];
+                }
+            }
+        }
+        flow
+    }
+
+    fn cost_of(&self, plans: &[Matrix<T>]) -> T {
+        self.tables
+            .iter()
+            .zip(plans.iter())
+            .fold(Default::default(), |acc, (table, plan)| {
+                acc + table.cost_of(plan)
+            })
+    }
+
+    pub fn report<V: Display>(&self, solution: &MultiCommoditySolution<T, V>) {
+        for (plan, table) in solution.plans.iter().zip(self.tables.iter()) {
+            println!("Commodity {} :", plan.name);
+            table.display(&plan.transport);
+            println!("Cost : {}", plan.cost);
+        }
+
+        // flow of every lane over all the commodities, next to its joint capacity
+        let mut builder = Builder::default();
+        let mut header = vec!["".to_string()];
+        for j in 0..self.capacity.cols() {
+            header.push(format!("D{}", j + 1));
+        }
+        builder.push_record(header);
+        for i in 0..self.capacity.rows() {
+            let mut row = vec![format!("S{}", i + 1)];
+            for j in 0..self.capacity.cols() {
+                let flow = solution.flow[(i, j)];
+                row.push(match self.capacity[(i, j)] {
+                    Some(capacity) => format!("{} / {}", flow, capacity),
+                    None => flow.to_string(),
+                });
+            }
+            builder.push_record(row);
+        }
+        println!("All commodities :");
+        println!(
+            "{}",
+            builder
+                .build()
+                .with(Style::rounded())
+                .with(Alignment::center())
+        );
+        println!("Total cost : {}", solution.total_cost);
+        println!("Lower bound : {}", solution.lower_bound);
+        println!("Iterations : {}", solution.iterations);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commodity(supply: Vec<u32>, demand: Vec<u32>) -> Table<u32> {
+        let costs = Matrix::new(vec![vec![1, 5], vec![5, 1]]);
+        Table::new(costs, Matrix::new_empty(2, 2), supply, demand)
+    }
+
+    #[test]
+    fn shared_lane_is_respected() {
+        // both commodities would send 3 units on S1-D1, which only carries 4
+        let mut problem: MultiCommodity<u32> = MultiCommodity::new(2, 2);
+        problem.add_commodity("A", commodity(vec![3, 3], vec![3, 3]));
+        problem.add_commodity("B", commodity(vec![3, 3], vec![3, 3]));
+        problem.set_capacity((0, 0), Some(4));
+
+        let solution = problem.solve::<i64>().unwrap();
+        assert!(solution.flow[(0, 0)] <= 4);
+        for (plan, table) in solution.plans.iter().zip(problem.tables.iter()) {
+            table.assert_ships_everything(&plan.transport, &plan.name);
+        }
+        assert!(solution.lower_bound <= solution.total_cost as i64);
+        // 4 units on S1-D1 and the 2 others around it, at 5 per unit on both legs
+        assert_eq!(solution.total_cost, 28);
+    }
+
+    #[test]
+    fn lane_too_small_for_any_plan() {
+        // S1 only reaches D1, through a lane narrower than what it has to ship
+        let costs = Matrix::new(vec![vec![1]]);
+        let mut problem: MultiCommodity<u32> = MultiCommodity::new(1, 1);
+        for name in ["A", "B"] {
+            let table = Table::new(costs.clone(), Matrix::new_empty(1, 1), vec![3], vec![3]);
+            problem.add_commodity(name, table);
+        }
+        problem.set_capacity((0, 0), Some(4));
+        problem.set_max_iterations(5);
+
+        let result = problem.solve::<i64>();
+        assert!(matches!(
+            result,
+            Err(SolveError::NoFeasiblePlan { iterations: 5 })
+        ));
+    }
+}
@@ -0,0 +1,127 @@
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    // the file could not be read at all
+    Io(String),
+    // a line ended before the expected value
+    MissingToken(&'static str),
+    // a value was found where the line or the file should have ended
+    TrailingData(String),
+    // the token is not a valid number
+    NotANumber(String),
+    // total supply and total demand differ
+    Unbalanced { supply: String, demand: String },
+    // the file has fewer rows than announced in its header
+    WrongDimensions { expected: usize, found: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: String,
+    // 1-based, 0 when the error is not tied to a line
+    pub line: usize,
+    // 1-based index of the token on the line, 0 when the error is about the whole line
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl ParseError {
+    pub fn new(file: &str, line: usize, column: usize, kind: ParseErrorKind) -> Self {
+        Self {
+            file: file.to_string(),
+            line,
+            column,
+            kind,
+        }
+    }
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorKind::Io(reason) => write!(f, "cannot read file: {}", reason),
+            ParseErrorKind::MissingToken(expected) => write!(f, "missing {}", expected),
+            ParseErrorKind::TrailingData(token) => {
+                write!(f, "unexpected trailing data '{}'", token)
+            }
+            ParseErrorKind::NotANumber(token) => write!(f, "'{}' is not a valid number", token),
+            ParseErrorKind::Unbalanced { supply, demand } => write!(
+                f,
+                "supply and demand are not balanced (total supply {}, total demand {})",
+                supply, demand
+            ),
+            ParseErrorKind::WrongDimensions { expected, found } => write!(
+                f,
+                "wrong dimensions, expected {} lines but found {}",
+                expected, found
+            ),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.file)?;
+        if self.line > 0 {
+            write!(f, ":{}", self.line)?;
+        }
+        if self.column > 0 {
+            write!(f, ":{}", self.column)?;
+        }
+        write!(f, ": {}", self.kind)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    // some flow could not be moved away from the forbidden cells
+    Infeasible { cells: Vec<(usize, usize)> },
+    // the route capacities do not let all the supply of these sources through
+    OverCapacity { sources: Vec<usize> },
+    // the network cannot carry all the supply of these nodes to a demand, or all the
+    // demand of these nodes from a supply
+    Unrouted { nodes: Vec<String> },
+    // the decomposition ran out of iterations before any plan met the joint capacities
+    NoFeasiblePlan { iterations: usize },
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::Infeasible { cells } => {
+                let routes: Vec<String> = cells
+                    .iter()
+                    .map(|(i, j)| format!("S{}-D{}", i + 1, j + 1))
+                    .collect();
+                write!(
+                    f,
+                    "the problem is infeasible, forbidden routes are still used: {}",
+                    routes.join(", ")
+                )
+            }
+            SolveError::OverCapacity { sources } => {
+                let sources: Vec<String> = sources.iter().map(|i| format!("S{}", i + 1)).collect();
+                write!(
+                    f,
+                    "the problem is infeasible, the capacities leave supply unshipped at: {}",
+                    sources.join(", ")
+                )
+            }
+            SolveError::Unrouted { nodes } => write!(
+                f,
+                "the problem is infeasible, supply or demand cannot be routed at: {}",
+                nodes.join(", ")
+            ),
+            SolveError::NoFeasiblePlan { iterations } => write!(
+                f,
+                "no plan within the joint capacities was found in {} iterations",
+                iterations
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
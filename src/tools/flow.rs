@@ -0,0 +1,249 @@
+// minimum cost flow over a Graph whose edges are read as arcs from `from` to `to`, solved by
+// successive shortest paths: Dijkstra on the reduced costs c_ij + pi_i - pi_j, which stay
+// non-negative as long as the potentials pi are updated with the distances of each round
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+use crate::tools::error::SolveError;
+use crate::tools::graph::{Edge, Graph};
+use crate::tools::number::{Number, Signed};
+
+pub struct FlowNetwork<T> {
+    // the weight of an edge is the cost of one unit, a pair of nodes is linked at most once
+    // in each direction
+    graph: Graph<T>,
+    // upper bound of each arc in the order of graph.edges, None when unbounded
+    capacity: Vec<Option<T>>,
+    // in the order of graph.vertices
+    supply: Vec<T>,
+    demand: Vec<T>,
+}
+
+pub struct Flow<T> {
+    // flow on each arc in the order of graph.edges
+    pub flows: Vec<T>,
+    pub total_cost: T,
+    // number of augmenting paths
+    pub iterations: usize,
+}
+
+// arc of the residual network, the reverse arc is at adjacency[to][reverse]
+struct Residual<T, V> {
+    to: usize,
+    capacity: Option<T>,
+    cost: V,
+    reverse: usize,
+}
+
+impl<T> FlowNetwork<T>
+where
+    T: Number,
+{
+    pub fn new() -> Self {
+        Self {
+            graph: Graph::new(),
+            capacity: Vec::new(),
+            supply: Vec::new(),
+            demand: Vec::new(),
+        }
+    }
+
+    pub fn add_node(&mut self, name: String, supply: T, demand: T) {
+        self.graph.add_node(name);
+        self.supply.push(supply);
+        self.demand.push(demand);
+    }
+
+    pub fn add_arc(&mut self, from: String, to: String, cost: T, capacity: Option<T>) {
+        // the edges of Graph are undirected, A -> B and B -> A are told apart here
+        if self
+            .graph
+            .edges
+            .iter()
+            .any(|edge| edge.from == from && edge.to == to)
+        {
+            panic!("Arc already exists");
+        }
+        self.graph.edges.push(Edge::new(from, to, cost));
+        self.capacity.push(capacity);
+    }
+
+    pub fn graph(&self) -> &Graph<T> {
+        &self.graph
+    }
+
+    pub fn solve<V>(&self) -> Result<Flow<T>, SolveError>
+    where
+        V: Signed,
+        T: Into<V>,
+    {
+        // the nodes of the graph, then a super source feeding every supply and a super
+        // sink draining every demand
+        let size = self.graph.vertices.len();
+        let (source, sink) = (size, size + 1);
+        let index: HashMap<&str, usize> = self
+            .graph
+            .vertices
+            .iter()
+            .enumerate()
+            .map(|(k, node)| (node.as_str(), k))
+            .collect();
+
+        let mut adjacency: Vec<Vec<Residual<T, V>>> = (0..size + 2).map(|_| Vec::new()).collect();
+        let mut add = |from: usize, to: usize, capacity: Option<T>, cost: T| {
+            let (forward, backward) = (adjacency[from].len(), adjacency[to].len());
+            adjacency[from].push(Residual {
+                to,
+                capacity,
+                cost: cost.into(),
+                reverse: backward,
+            });
+            adjacency[to].push(Residual {
+                to: from,
+                capacity: Some(Default::default()),
+                cost: V::default() - cost.into(),
+                reverse: forward,
+            });
+            (from, forward)
+        };
+        let arcs: Vec<(usize, usize)> = self
+            .graph
+            .edges
+            .iter()
+            .zip(self.capacity.iter())
+            .map(|(edge, &capacity)| {
+                add(
+                    index[edge.from.as_str()],
+                    index[edge.to.as_str()],
+                    capacity,
+                    edge.weight,
+                )
+            })
+            .collect();
+        for k in 0..size {
+            add(source, k, Some(self.supply[k]), Default::default());
+            add(k, sink, Some(self.demand[k]), Default::default());
+        }
+
+        // the costs are non-negative, so zero potentials are a valid start
+        let mut potentials: Vec<V> = vec![Default::default(); size + 2];
+        let mut iterations = 0;
+        loop {
+            // Dijkstra on the reduced costs, remembering the arc used to reach each node
+            let mut distance: Vec<Option<V>> = vec![None; size + 2];
+            let mut previous: Vec<Option<(usize, usize)>> = vec![None; size + 2];
+            let mut heap = BinaryHeap::new();
+            distance[source] = Some(Default::default());
+            heap.push(Reverse((V::default(), source)));
+            while let Some(Reverse((d, node))) = heap.pop() {
+                if distance[node].is_some_and(|best| best < d) {
+                    continue;
+                }
+                for (k, arc) in adjacency[node].iter().enumerate() {
+                    if arc.capacity == Some(Default::default()) {
+                        continue;
+                    }
+                    let next = d + arc.cost + potentials[node] - potentials[arc.to];
+                    if distance[arc.to].is_none_or(|best| next < best) {
+                        distance[arc.to] = Some(next);
+                        previous[arc.to] = Some((node, k));
+                        heap.push(Reverse((next, arc.to)));
+                    }
+                }
+            }
+
+            let reached = match distance[sink] {
+                Some(reached) => reached,
+                None => break,
+            };
+            // nodes further than the sink are moved by the sink distance only, which keeps
+            // every reduced cost non-negative
+            for node in 0..size + 2 {
+                let d = distance[node].map_or(reached, |d| d.min(reached));
+                potentials[node] = potentials[node] + d;
+            }
+
+            // push as much as the path allows, the super arcs bound it
+            let mut path = Vec::new();
+            let mut node = sink;
+            while let Some((from, k)) = previous[node] {
+                path.push((from, k));
+                node = from;
+            }
+            let amount = path
+                .iter()
+                .filter_map(|&(from, k)| adjacency[from][k].capacity)
+                .min()
+                .expect("The augmenting path is unbounded");
+            for &(from, k) in path.iter() {
+                let arc = &mut adjacency[from][k];
+                if let Some(capacity) = arc.capacity.as_mut() {
+                    *capacity -= amount;
+                }
+                let (to, reverse) = (arc.to, arc.reverse);
+                if let Some(capacity) = adjacency[to][reverse].capacity.as_mut() {
+                    *capacity += amount;
+                }
+            }
+            iterations += 1;
+        }
+
+        // the supply left on the super source arcs could not be routed
+        let nodes: Vec<String> = adjacency[source]
+            .iter()
+            .filter(|arc| arc.capacity.is_some_and(|left| left != Default::default()))
+            .map(|arc| self.graph.vertices[arc.to].clone())
+            .collect();
+        if !nodes.is_empty() {
+            return Err(SolveError::Unrouted { nodes });
+        }
+
+        // the flow on an arc is the capacity of its reverse residual arc
+        let flows: Vec<T> = arcs
+            .iter()
+            .map(|&(from, k)| {
+                let arc = &adjacency[from][k];
+                adjacency[arc.to][arc.reverse].capacity.unwrap()
+            })
+            .collect();
+        let total_cost = self
+            .graph
+            .edges
+            .iter()
+            .zip(flows.iter())
+            .fold(Default::default(), |acc: T, (edge, &flow)| {
+                acc + edge.weight * flow
+            });
+        Ok(Flow {
+            flows,
+            total_cost,
+            iterations,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arcs_in_both_directions() {
+        // A ships 4 to B, straight at 5 per unit or through C at 2, with 3 units at most
+        // on A -> C; B -> A is a separate arc and stays empty
+        let mut network: FlowNetwork<u32> = FlowNetwork::new();
+        network.add_node("A".to_string(), 4, 0);
+        network.add_node("B".to_string(), 0, 4);
+        network.add_node("C".to_string(), 0, 0);
+        network.add_arc("A".to_string(), "B".to_string(), 5, None);
+        network.add_arc("B".to_string(), "A".to_string(), 1, None);
+        network.add_arc("A".to_string(), "C".to_string(), 1, Some(3));
+        network.add_arc("C".to_string(), "B".to_string(), 1, None);
+
+        let flow = network.solve::<i64>().unwrap();
+        assert_eq!(flow.flows, vec![1, 0, 3, 3]);
+        assert_eq!(flow.total_cost, 11);
+    }
+}
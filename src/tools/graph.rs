@@ -8,9 +8,10 @@ use rand::SeedableRng;
 use std::{
     collections::{HashMap, HashSet, VecDeque},
     fmt::Debug,
+    hash::{Hash, Hasher},
 };
 
-#[derive(Clone, Eq, Hash)]
+#[derive(Clone, Eq)]
 pub struct Edge<T> {
     pub from: String,
     pub to: String,
@@ -24,6 +25,19 @@ impl<T> PartialEq for Edge<T> {
     }
 }
 
+impl<T> Hash for Edge<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // edges are undirected, hash both ends in a fixed order to stay consistent with eq
+        if self.from <= self.to {
+            self.from.hash(state);
+            self.to.hash(state);
+        } else {
+            self.to.hash(state);
+            self.from.hash(state);
+        }
+    }
+}
+
 impl<T> Edge<T> {
     pub fn new(from: String, to: String, weight: T) -> Self {
         Self { from, to, weight }
@@ -36,11 +50,30 @@ impl<T> Debug for Edge<T> {
     }
 }
 
+// side of a stepping-stone cycle an edge is on: receiving (+) or giving (-) flow
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sign {
+    Plus,
+    Minus,
+}
+
+// order in which candidate edges are tried when a degenerate basis has to be completed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Degeneracy {
+    // cheapest edges first, ties kept in the order they were given
+    #[default]
+    CheapestCost,
+    // edges sorted by their end points, S1-D1, S1-D2, ..., S2-D1, ...
+    Lexicographic,
+    // cheapest edges first, ties shuffled with the given seed
+    Seeded(u64),
+}
+
 #[derive(Debug, Clone)]
 pub struct Graph<T> {
     pub vertices: Vec<String>,
     pub edges: Vec<Edge<T>>,
-    seed: u64,
+    degeneracy: Degeneracy,
 }
 
 impl<T> Graph<T>
@@ -51,14 +84,18 @@ where
         Self {
             vertices: Vec::new(),
             edges: Vec::new(),
-            // init seed with current time
-            seed: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
+            degeneracy: Degeneracy::default(),
         }
     }
 
+    pub fn degeneracy(&self) -> Degeneracy {
+        self.degeneracy
+    }
+
+    pub fn set_degeneracy(&mut self, degeneracy: Degeneracy) {
+        self.degeneracy = degeneracy;
+    }
+
     pub fn add_node(&mut self, node: String) {
         if self.vertices.contains(&node) {
             panic!("Node already exists");
@@ -83,6 +120,98 @@ where
         }
     }
 
+    pub fn remove_edge(&mut self, from: &str, to: &str) -> Option<Edge<T>> {
+        let index = self.edges.iter().position(|edge| {
+            edge.from == from && edge.to == to || edge.from == to && edge.to == from
+        })?;
+        Some(self.edges.remove(index))
+    }
+
+    pub fn path(&self, from: &str, to: &str) -> Option<Vec<Edge<T>>> {
+        // breadth first search from `from`, remembering the edge used to reach each node
+        let mut previous: HashMap<&str, &Edge<T>> = HashMap::new();
+        let mut visited: HashSet<&str> = HashSet::new();
+        let mut queue = VecDeque::new();
+
+        visited.insert(from);
+        queue.push_back(from);
+
+        while let Some(node) = queue.pop_front() {
+            if node == to {
+                break;
+            }
+            for edge in self.edges.iter() {
+                let next = if edge.from == node {
+                    edge.to.as_str()
+                } else if edge.to == node {
+                    edge.from.as_str()
+                } else {
+                    continue;
+                };
+                if visited.insert(next) {
+                    previous.insert(next, edge);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        if !visited.contains(to) {
+            return None;
+        }
+
+        // walk back from `to` to `from` and reverse to get the ordered path
+        let mut path = Vec::new();
+        let mut node = to;
+        while node != from {
+            let edge = previous[node];
+            node = if edge.from == node {
+                edge.to.as_str()
+            } else {
+                edge.from.as_str()
+            };
+            path.push(edge.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    pub fn stepping_stone(&self, entering: &Edge<T>) -> Option<Vec<(Edge<T>, Sign)>> {
+        // in a tree the entering edge closes exactly one cycle: itself followed by the
+        // tree path from its end back to its start, with signs alternating along the way
+        if self.edges.contains(entering) {
+            return None;
+        }
+        let path = self.path(&entering.to, &entering.from)?;
+
+        let mut cycle = vec![(entering.clone(), Sign::Plus)];
+        for (k, edge) in path.into_iter().enumerate() {
+            let sign = if k % 2 == 0 { Sign::Minus } else { Sign::Plus };
+            cycle.push((edge, sign));
+        }
+        Some(cycle)
+    }
+
+    pub fn component_without(&self, start: &str, excluded: &Edge<T>) -> HashSet<String> {
+        // nodes reachable from `start` when `excluded` is cut out of the graph
+        let mut adjacency: HashMap<&str, Vec<&str>> = HashMap::new();
+        for edge in self.edges.iter().filter(|&edge| edge != excluded) {
+            adjacency.entry(&edge.from).or_default().push(&edge.to);
+            adjacency.entry(&edge.to).or_default().push(&edge.from);
+        }
+
+        let mut component = HashSet::new();
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            if !component.insert(node.to_string()) {
+                continue;
+            }
+            if let Some(neighbours) = adjacency.get(node) {
+                stack.extend(neighbours.iter().filter(|&&next| !component.contains(next)));
+            }
+        }
+        component
+    }
+
     pub fn is_connected(&self) -> bool {
         let mut visited = HashMap::new();
         let mut stack = Vec::new();
@@ -171,7 +300,6 @@ where
             node: String,
             parent: String,
             visited: &mut HashSet<String>,
-            path: &mut Vec<Edge<T>>,
         ) -> Option<Vec<Edge<T>>>
         where
             T: Clone,
@@ -191,8 +319,7 @@ where
                     }
 
                     if !visited.contains(&next_node) {
-                        if let Some(mut cycle) = dfs(graph, next_node, node.clone(), visited, path)
-                        {
+                        if let Some(mut cycle) = dfs(graph, next_node, node.clone(), visited) {
                             cycle.push(edge.clone());
                             return Some(cycle);
                         }
@@ -202,19 +329,22 @@ where
             None
         }
 
-        return dfs(
-            self,
-            self.vertices[0].clone(),
-            String::new(),
-            &mut visited,
-            &mut Vec::new(),
-        );
+        dfs(self, self.vertices[0].clone(), String::new(), &mut visited)
     }
 
     pub fn k_edge_augmentation(
+        &mut self,
+        k: usize,
+        edges: Vec<Edge<T>>,
+    ) -> Result<(), &'static str> {
+        self.resolve_degeneracy(k, edges, self.degeneracy)
+    }
+
+    pub fn resolve_degeneracy(
         &mut self,
         k: usize,
         mut edges: Vec<Edge<T>>,
+        degeneracy: Degeneracy,
     ) -> Result<(), &'static str> {
         // The k-edge augmentation is a technique used to increase the connectivity of a graph by adding k edges to the graph
 
@@ -227,13 +357,23 @@ where
         }
 
         let mut k = k;
-        // seed the random number generator
-        let mut rng = rand::rngs::StdRng::seed_from_u64(self.seed);
-        // shuffle the edges
-        edges.shuffle(&mut rng);
-
-        // step 1: sort the new edges by their weight
-        edges.sort_by(|a, b| a.weight.partial_cmp(&b.weight).unwrap());
+        // step 1: order the new edges according to the strategy, the sorts are stable so
+        // the result only depends on the given edges (and the seed)
+        match degeneracy {
+            Degeneracy::CheapestCost => {
+                edges.sort_by(|a, b| a.weight.partial_cmp(&b.weight).unwrap());
+            }
+            Degeneracy::Lexicographic => {
+                edges.sort_by(|a, b| {
+                    (node_key(&a.from), node_key(&a.to)).cmp(&(node_key(&b.from), node_key(&b.to)))
+                });
+            }
+            Degeneracy::Seeded(seed) => {
+                let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+                edges.shuffle(&mut rng);
+                edges.sort_by(|a, b| a.weight.partial_cmp(&b.weight).unwrap());
+            }
+        }
 
         // step 2: add the new edges to the graph
         for edge in edges {
@@ -261,11 +401,11 @@ where
         }
         Ok(())
     }
+}
 
-    pub fn update_seed(&mut self) {
-        self.seed = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-    }
+// split a node name into its prefix and index so that S2 comes before S10
+fn node_key(node: &str) -> (&str, usize) {
+    let split = node.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+    let (prefix, index) = node.split_at(split);
+    (prefix, index.parse().unwrap_or(0))
 }
@@ -0,0 +1,90 @@
+// Hungarian algorithm for the square assignment problem, every row is matched to exactly
+// one column: O(n^3) with row and column potentials, one shortest augmenting path per row
+
+use std::ops::Add;
+
+use crate::tools::matrix::Matrix;
+use crate::tools::number::Signed;
+
+pub struct Assignment<T> {
+    // (row, column) pairs, one per row in row order
+    pub pairs: Vec<(usize, usize)>,
+    pub cost: T,
+}
+
+pub fn hungarian<T, V>(costs: &Matrix<T>) -> Assignment<T>
+where
+    T: Default + Clone + Copy + Add<Output = T> + Into<V>,
+    V: Signed,
+{
+    assert!(
+        costs.is_square(),
+        "The assignment problem needs a square matrix"
+    );
+    let n = costs.rows();
+
+    // 1-based, index 0 of the columns is the row currently being inserted
+    let mut u: Vec<V> = vec![Default::default(); n + 1];
+    let mut v: Vec<V> = vec![Default::default(); n + 1];
+    // row matched to each column and previous column on the augmenting path
+    let mut matched = vec![0; n + 1];
+    let mut way = vec![0; n + 1];
+
+    for i in 1..=n {
+        matched[0] = i;
+        let mut j0 = 0;
+        let mut min_slack: Vec<Option<V>> = vec![None; n + 1];
+        let mut used = vec![false; n + 1];
+
+        // grow a shortest path tree from row i until it reaches a free column
+        loop {
+            used[j0] = true;
+            let i0 = matched[j0];
+            let mut delta: Option<V> = None;
+            let mut j1 = 0;
+            for j in 1..=n {
+                if used[j] {
+                    continue;
+                }
+                let slack = costs[(i0 - 1, j - 1)].into() - u[i0] - v[j];
+                if min_slack[j].is_none_or(|min| slack < min) {
+                    min_slack[j] = Some(slack);
+                    way[j] = j0;
+                }
+                let slack = min_slack[j].unwrap();
+                if delta.is_none_or(|delta| slack < delta) {
+                    delta = Some(slack);
+                    j1 = j;
+                }
+            }
+
+            let delta = delta.expect("Every column is already used");
+            for j in 0..=n {
+                if used[j] {
+                    u[matched[j]] = u[matched[j]] + delta;
+                    v[j] -= delta;
+                } else {
+                    min_slack[j] = min_slack[j].map(|slack| slack - delta);
+                }
+            }
+            j0 = j1;
+            if matched[j0] == 0 {
+                break;
+            }
+        }
+
+        // flip the matching along the augmenting path
+        while j0 != 0 {
+            let j1 = way[j0];
+            matched[j0] = matched[j1];
+            j0 = j1;
+        }
+    }
+
+    let mut pairs: Vec<(usize, usize)> = (1..=n).map(|j| (matched[j] - 1, j - 1)).collect();
+    pairs.sort();
+    let cost = pairs
+        .iter()
+        .fold(Default::default(), |acc, &cell| acc + costs[cell]);
+    Assignment { pairs, cost }
+}
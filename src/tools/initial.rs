@@ -0,0 +1,458 @@
+// initial basic feasible solutions of the transportation problem
+// every heuristic implements the InitialSolution trait so new ones can be registered
+// without touching the table itself
+
+use std::{
+    ops::{Add, Sub, SubAssign},
+    str::FromStr,
+};
+
+use crate::tools::matrix::Matrix;
+
+pub trait InitialSolution<T> {
+    fn name(&self) -> &str;
+
+    // returns the transport matrix and the cells chosen as basic, in the order they were filled
+    fn solve(
+        &self,
+        costs: &Matrix<T>,
+        supply: &[T],
+        demand: &[T],
+    ) -> (Matrix<T>, Vec<(usize, usize)>);
+}
+
+// state shared by the heuristics: what is left to ship and which lines are still open
+struct Allocation<T> {
+    transport: Matrix<T>,
+    basis: Vec<(usize, usize)>,
+    supply: Vec<T>,
+    demand: Vec<T>,
+    rows: Vec<bool>,
+    cols: Vec<bool>,
+}
+
+impl<T> Allocation<T>
+where
+    T: Default + Copy + Ord + SubAssign,
+{
+    fn new(supply: &[T], demand: &[T]) -> Self {
+        Self {
+            transport: Matrix::new_empty(supply.len(), demand.len()),
+            basis: Vec::new(),
+            supply: supply.to_vec(),
+            demand: demand.to_vec(),
+            rows: vec![true; supply.len()],
+            cols: vec![true; demand.len()],
+        }
+    }
+
+    fn open_rows(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.rows.len()).filter(|&i| self.rows[i])
+    }
+
+    fn open_cols(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.cols.len()).filter(|&j| self.cols[j])
+    }
+
+    fn allocate(&mut self, (i, j): (usize, usize)) {
+        // ship as much as possible through the cell, the cell is basic even if nothing is shipped
+        let min = std::cmp::min(self.supply[i], self.demand[j]);
+        self.transport[(i, j)] = min;
+        self.basis.push((i, j));
+        self.supply[i] -= min;
+        self.demand[j] -= min;
+
+        // cross out a single line per allocation, except for the last one, so that the basis
+        // ends with exactly n + m - 1 cells: when both lines are exhausted the row goes first
+        // and the column stays open with a zero demand
+        let row_exhausted = self.supply[i] == Default::default();
+        let col_exhausted = self.demand[j] == Default::default();
+        let last_row = self.open_rows().count() == 1;
+        let last_col = self.open_cols().count() == 1;
+        if row_exhausted && col_exhausted && last_row && last_col {
+            self.rows[i] = false;
+            self.cols[j] = false;
+        } else if row_exhausted && (!col_exhausted || !last_row) {
+            self.rows[i] = false;
+        } else if col_exhausted {
+            self.cols[j] = false;
+        }
+    }
+
+    fn finish(self) -> (Matrix<T>, Vec<(usize, usize)>) {
+        (self.transport, self.basis)
+    }
+}
+
+pub struct NorthWestCorner;
+
+impl<T> InitialSolution<T> for NorthWestCorner
+where
+    T: Default + Copy + Ord + SubAssign,
+{
+    fn name(&self) -> &str {
+        Initializer::NorthWestCorner.name()
+    }
+
+    fn solve(
+        &self,
+        _costs: &Matrix<T>,
+        supply: &[T],
+        demand: &[T],
+    ) -> (Matrix<T>, Vec<(usize, usize)>) {
+        let mut allocation = Allocation::new(supply, demand);
+        let mut i = 0;
+        let mut j = 0;
+        while i < supply.len() && j < demand.len() {
+            allocation.allocate((i, j));
+            if !allocation.rows[i] {
+                i += 1;
+            }
+            if !allocation.cols[j] {
+                j += 1;
+            }
+        }
+        allocation.finish()
+    }
+}
+
+pub struct BalasHammer;
+
+impl<T> InitialSolution<T> for BalasHammer
+where
+    T: Default + Copy + Ord + Sub<Output = T> + SubAssign,
+{
+    fn name(&self) -> &str {
+        Initializer::BalasHammer.name()
+    }
+
+    fn solve(
+        &self,
+        costs: &Matrix<T>,
+        supply: &[T],
+        demand: &[T],
+    ) -> (Matrix<T>, Vec<(usize, usize)>) {
+        // penalty of a line: difference between its two smallest costs (the cost itself if it is alone)
+        // ties on the cheapest cell are broken by the lowest index
+        fn penalty<T>(costs: impl Iterator<Item = (usize, T)>) -> Option<(T, usize)>
+        where
+            T: Copy + Ord + Sub<Output = T>,
+        {
+            let mut first: Option<(usize, T)> = None;
+            let mut second: Option<T> = None;
+            for (k, cost) in costs {
+                match first {
+                    Some((_, min)) if cost >= min => {
+                        if second.is_none_or(|second| cost < second) {
+                            second = Some(cost);
+                        }
+                    }
+                    _ => {
+                        second = first.map(|(_, min)| min);
+                        first = Some((k, cost));
+                    }
+                }
+            }
+            let (k, min) = first?;
+            Some((second.map_or(min, |second| second - min), k))
+        }
+
+        let mut allocation = Allocation::new(supply, demand);
+        loop {
+            // select the line with the highest penalty, rows first then columns, lowest index first
+            let mut best: Option<(T, (usize, usize))> = None;
+            for i in allocation.open_rows() {
+                let line = allocation.open_cols().map(|j| (j, costs[(i, j)]));
+                if let Some((p, j)) = penalty(line) {
+                    if best.is_none_or(|(max, _)| p > max) {
+                        best = Some((p, (i, j)));
+                    }
+                }
+            }
+            for j in allocation.open_cols() {
+                let line = allocation.open_rows().map(|i| (i, costs[(i, j)]));
+                if let Some((p, i)) = penalty(line) {
+                    if best.is_none_or(|(max, _)| p > max) {
+                        best = Some((p, (i, j)));
+                    }
+                }
+            }
+
+            // fill the cheapest cell of the selected line
+            match best {
+                Some((_, cell)) => allocation.allocate(cell),
+                None => break,
+            }
+        }
+        allocation.finish()
+    }
+}
+
+pub struct LeastCost;
+
+impl<T> InitialSolution<T> for LeastCost
+where
+    T: Default + Copy + Ord + SubAssign,
+{
+    fn name(&self) -> &str {
+        Initializer::LeastCost.name()
+    }
+
+    fn solve(
+        &self,
+        costs: &Matrix<T>,
+        supply: &[T],
+        demand: &[T],
+    ) -> (Matrix<T>, Vec<(usize, usize)>) {
+        let mut allocation = Allocation::new(supply, demand);
+        loop {
+            // cheapest cell of the remaining matrix, lowest (row, column) first on ties
+            let mut best: Option<(T, (usize, usize))> = None;
+            for i in allocation.open_rows() {
+                for j in allocation.open_cols() {
+                    if best.is_none_or(|(min, _)| costs[(i, j)] < min) {
+                        best = Some((costs[(i, j)], (i, j)));
+                    }
+                }
+            }
+            match best {
+                Some((_, cell)) => allocation.allocate(cell),
+                None => break,
+            }
+        }
+        allocation.finish()
+    }
+}
+
+pub struct RowMinimum;
+
+impl<T> InitialSolution<T> for RowMinimum
+where
+    T: Default + Copy + Ord + SubAssign,
+{
+    fn name(&self) -> &str {
+        Initializer::RowMinimum.name()
+    }
+
+    fn solve(
+        &self,
+        costs: &Matrix<T>,
+        supply: &[T],
+        demand: &[T],
+    ) -> (Matrix<T>, Vec<(usize, usize)>) {
+        let mut allocation = Allocation::new(supply, demand);
+        // exhaust the rows in order, each time through its cheapest remaining column
+        loop {
+            let i = match allocation.open_rows().next() {
+                Some(i) => i,
+                None => break,
+            };
+            let j = match allocation.open_cols().min_by_key(|&j| costs[(i, j)]) {
+                Some(j) => j,
+                None => break,
+            };
+            allocation.allocate((i, j));
+        }
+        allocation.finish()
+    }
+}
+
+pub struct ColumnMinimum;
+
+impl<T> InitialSolution<T> for ColumnMinimum
+where
+    T: Default + Copy + Ord + SubAssign,
+{
+    fn name(&self) -> &str {
+        Initializer::ColumnMinimum.name()
+    }
+
+    fn solve(
+        &self,
+        costs: &Matrix<T>,
+        supply: &[T],
+        demand: &[T],
+    ) -> (Matrix<T>, Vec<(usize, usize)>) {
+        let mut allocation = Allocation::new(supply, demand);
+        // exhaust the columns in order, each time through its cheapest remaining row
+        loop {
+            let j = match allocation.open_cols().next() {
+                Some(j) => j,
+                None => break,
+            };
+            let i = match allocation.open_rows().min_by_key(|&i| costs[(i, j)]) {
+                Some(i) => i,
+                None => break,
+            };
+            allocation.allocate((i, j));
+        }
+        allocation.finish()
+    }
+}
+
+pub struct Russell;
+
+impl<T> InitialSolution<T> for Russell
+where
+    T: Default + Copy + Ord + Add<Output = T> + Sub<Output = T> + SubAssign,
+{
+    fn name(&self) -> &str {
+        Initializer::Russell.name()
+    }
+
+    fn solve(
+        &self,
+        costs: &Matrix<T>,
+        supply: &[T],
+        demand: &[T],
+    ) -> (Matrix<T>, Vec<(usize, usize)>) {
+        let mut allocation = Allocation::new(supply, demand);
+        loop {
+            // u_i and v_j are the highest remaining costs of each row and column
+            let mut u = vec![None; supply.len()];
+            let mut v = vec![None; demand.len()];
+            for i in allocation.open_rows() {
+                for j in allocation.open_cols() {
+                    u[i] = std::cmp::max(u[i], Some(costs[(i, j)]));
+                    v[j] = std::cmp::max(v[j], Some(costs[(i, j)]));
+                }
+            }
+
+            // the most negative delta_ij = c_ij - u_i - v_j is the highest u_i + v_j - c_ij,
+            // which stays positive for unsigned costs, lowest (row, column) first on ties
+            let mut best: Option<(T, (usize, usize))> = None;
+            for i in allocation.open_rows() {
+                for j in allocation.open_cols() {
+                    if let (Some(u), Some(v)) = (u[i], v[j]) {
+                        let delta = u + v - costs[(i, j)];
+                        if best.is_none_or(|(max, _)| delta > max) {
+                            best = Some((delta, (i, j)));
+                        }
+                    }
+                }
+            }
+            match best {
+                Some((_, cell)) => allocation.allocate(cell),
+                None => break,
+            }
+        }
+        allocation.finish()
+    }
+}
+
+// the built-in heuristics, selectable by name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Initializer {
+    NorthWestCorner,
+    BalasHammer,
+    LeastCost,
+    RowMinimum,
+    ColumnMinimum,
+    Russell,
+}
+
+impl Initializer {
+    pub const ALL: [Initializer; 6] = [
+        Initializer::NorthWestCorner,
+        Initializer::BalasHammer,
+        Initializer::LeastCost,
+        Initializer::RowMinimum,
+        Initializer::ColumnMinimum,
+        Initializer::Russell,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Initializer::NorthWestCorner => "north-west-corner",
+            Initializer::BalasHammer => "balas-hammer",
+            Initializer::LeastCost => "least-cost",
+            Initializer::RowMinimum => "row-minimum",
+            Initializer::ColumnMinimum => "column-minimum",
+            Initializer::Russell => "russell",
+        }
+    }
+}
+
+impl<T> InitialSolution<T> for Initializer
+where
+    T: Default + Copy + Ord + Add<Output = T> + Sub<Output = T> + SubAssign,
+{
+    fn name(&self) -> &str {
+        Initializer::name(self)
+    }
+
+    fn solve(
+        &self,
+        costs: &Matrix<T>,
+        supply: &[T],
+        demand: &[T],
+    ) -> (Matrix<T>, Vec<(usize, usize)>) {
+        match self {
+            Initializer::NorthWestCorner => NorthWestCorner.solve(costs, supply, demand),
+            Initializer::BalasHammer => BalasHammer.solve(costs, supply, demand),
+            Initializer::LeastCost => LeastCost.solve(costs, supply, demand),
+            Initializer::RowMinimum => RowMinimum.solve(costs, supply, demand),
+            Initializer::ColumnMinimum => ColumnMinimum.solve(costs, supply, demand),
+            Initializer::Russell => Russell.solve(costs, supply, demand),
+        }
+    }
+}
+
+impl FromStr for Initializer {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Initializer::ALL
+            .into_iter()
+            .find(|initializer| initializer.name() == name)
+            .ok_or_else(|| format!("Unknown initializer: {}", name))
+    }
+}
+
+// heuristics selectable by name, starting with the built-in ones
+pub struct Registry<T> {
+    heuristics: Vec<Box<dyn InitialSolution<T>>>,
+}
+
+impl<T> Registry<T> {
+    pub fn new() -> Self {
+        Self {
+            heuristics: Vec::new(),
+        }
+    }
+
+    pub fn register(&mut self, heuristic: Box<dyn InitialSolution<T>>) {
+        if self.get(heuristic.name()).is_some() {
+            panic!("Initializer already registered");
+        }
+        self.heuristics.push(heuristic);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn InitialSolution<T>> {
+        self.heuristics
+            .iter()
+            .find(|heuristic| heuristic.name() == name)
+            .map(|heuristic| heuristic.as_ref())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn InitialSolution<T>> {
+        self.heuristics.iter().map(|heuristic| heuristic.as_ref())
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.iter().map(|heuristic| heuristic.name()).collect()
+    }
+}
+
+impl<T> Default for Registry<T>
+where
+    T: Default + Copy + Ord + Add<Output = T> + Sub<Output = T> + SubAssign + 'static,
+{
+    fn default() -> Self {
+        let mut registry = Self::new();
+        for initializer in Initializer::ALL {
+            registry.register(Box::new(initializer));
+        }
+        registry
+    }
+}
@@ -50,6 +50,21 @@ where
         &self.data
     }
 
+    pub fn push_row(&mut self, row: Vec<T>) {
+        assert_eq!(row.len(), self.cols);
+        self.data.push(row);
+        self.rows += 1;
+    }
+
+    pub fn push_col(&mut self, col: Vec<T>) {
+        assert_eq!(col.len(), self.rows);
+        for (row, value) in self.data.iter_mut().zip(col) {
+            row.push(value);
+        }
+        self.cols += 1;
+    }
+
+    #[allow(clippy::needless_range_loop)]
     pub fn transpose(&self) -> Self {
         let mut transposed = vec![vec![Default::default(); self.rows()]; self.cols()];
         for i in 0..self.rows() {
@@ -106,7 +121,8 @@ impl<T> Matrix<T>
 where
     T: Default + Clone + Copy + Debug,
 {
-    pub fn solve<U, V>(&self, b: &Vec<U>) -> Vec<V>
+    #[allow(clippy::needless_range_loop)]
+    pub fn solve<U, V>(&self, b: &[U]) -> Vec<V>
     where
         V: Default
             + Clone
@@ -200,6 +216,7 @@ where
 {
     type Output = Self;
 
+    #[allow(clippy::needless_range_loop)]
     fn add(self, other: Self) -> Self {
         let mut sum = vec![vec![Default::default(); self.cols()]; self.rows()];
         for i in 0..self.rows() {
@@ -217,6 +234,7 @@ where
 {
     type Output = Self;
 
+    #[allow(clippy::needless_range_loop)]
     fn sub(self, other: Self) -> Self {
         let mut diff = vec![vec![Default::default(); self.cols()]; self.rows()];
         for i in 0..self.rows() {
@@ -234,6 +252,7 @@ where
 {
     type Output = Self;
 
+    #[allow(clippy::needless_range_loop)]
     fn mul(self, other: Self) -> Self {
         let mut product = vec![vec![Default::default(); other.cols()]; self.rows()];
         for i in 0..self.rows() {
@@ -253,6 +272,7 @@ where
 {
     type Output = Self;
 
+    #[allow(clippy::needless_range_loop)]
     fn mul(self, scalar: T) -> Self {
         let mut product = vec![vec![Default::default(); self.cols()]; self.rows()];
         for i in 0..self.rows() {
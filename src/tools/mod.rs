@@ -1,3 +1,12 @@
+pub mod commodity;
+pub mod error;
+pub mod flow;
 pub mod graph;
+pub mod hungarian;
+pub mod initial;
 pub mod matrix;
+pub mod number;
+pub mod period;
+pub mod simplex;
 pub mod table;
+pub mod transshipment;
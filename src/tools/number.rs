@@ -0,0 +1,79 @@
+// numeric bounds shared by the solvers, spelled out once and implemented for every type
+// that satisfies them
+
+use std::{
+    fmt::{Debug, Display},
+    iter::Sum,
+    num::ParseIntError,
+    ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+// quantities and costs as they are read from the data files
+pub trait Number:
+    Default
+    + Display
+    + Debug
+    + Copy
+    + Ord
+    + From<u8>
+    + FromStr<Err = ParseIntError>
+    + Sum
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + AddAssign
+    + SubAssign
+{
+}
+
+impl<T> Number for T where
+    T: Default
+        + Display
+        + Debug
+        + Copy
+        + Ord
+        + From<u8>
+        + FromStr<Err = ParseIntError>
+        + Sum
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + AddAssign
+        + SubAssign
+{
+}
+
+// signed type the potentials, marginal costs and big-M are computed in, wider than the
+// input type
+pub trait Signed:
+    Default
+    + Debug
+    + Copy
+    + Ord
+    + From<i8>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + SubAssign
+{
+}
+
+impl<V> Signed for V where
+    V: Default
+        + Debug
+        + Copy
+        + Ord
+        + From<i8>
+        + Add<Output = V>
+        + Sub<Output = V>
+        + Mul<Output = V>
+        + Div<Output = V>
+        + Neg<Output = V>
+        + SubAssign
+{
+}
@@ -0,0 +1,223 @@
+// planning over several periods: what a source supplies in period t may be shipped in any
+// later period t' at the cost of period t' plus the holding cost of t' - t periods of
+// storage. the periods are expanded into one table whose sources and destinations are
+// indexed by period, shipping backwards in time being forbidden
+
+use crate::tools::error::SolveError;
+use crate::tools::initial::Initializer;
+use crate::tools::matrix::Matrix;
+use crate::tools::number::{Number, Signed};
+use crate::tools::table::{Objective, Table};
+
+pub struct MultiPeriod<T> {
+    periods: Vec<Table<T>>,
+    // cost of keeping one unit at a source from one period to the next
+    holding: Vec<T>,
+    n: usize,
+    m: usize,
+}
+
+pub struct Schedule<T> {
+    // what is shipped during each period, whatever the period it was supplied in
+    pub shipments: Vec<Matrix<T>>,
+    // stock left at each source at the end of each period, waiting to be shipped later
+    pub inventory: Vec<Vec<T>>,
+    // demand of each period met by no source and supply of each period never shipped, when
+    // the horizon is unbalanced
+    pub unmet: Vec<Vec<T>>,
+    pub surplus: Vec<Vec<T>>,
+    pub shipping_cost: T,
+    pub holding_cost: T,
+    pub iterations: usize,
+}
+
+impl<T> MultiPeriod<T>
+where
+    T: Number,
+{
+    pub fn new(n: usize, m: usize) -> Self {
+        Self {
+            periods: Vec::new(),
+            holding: vec![Default::default(); n],
+            n,
+            m,
+        }
+    }
+
+    pub fn add_period(&mut self, table: Table<T>) {
+        assert_eq!(
+            (table.supply().len(), table.demand().len()),
+            (self.n, self.m),
+            "Every period must use the same sources and destinations"
+        );
+        assert_eq!(table.objective(), Objective::Minimize);
+        // a lane capacity would be shared by all the periods feeding it
+        assert!(
+            !table.is_capacitated(),
+            "Capacities are not supported over several periods"
+        );
+        self.periods.push(table);
+    }
+
+    pub fn set_holding_cost(&mut self, source: usize, cost: T) {
+        self.holding[source] = cost;
+    }
+
+    pub fn periods(&self) -> &Vec<Table<T>> {
+        &self.periods
+    }
+
+    fn storage(&self, source: usize, periods: usize) -> T {
+        (0..periods).fold(Default::default(), |acc, _| acc + self.holding[source])
+    }
+
+    pub fn to_table(&self) -> Table<T> {
+        // source (t, i) is row t * n + i and destination (t', j) is column t' * m + j
+        let count = self.periods.len();
+        let (rows, cols) = (count * self.n, count * self.m);
+        let mut costs = Matrix::new_empty(rows, cols);
+        let mut supply = Vec::with_capacity(rows);
+        let mut demand = Vec::with_capacity(cols);
+        for table in self.periods.iter() {
+            supply.extend(table.supply().iter().copied());
+            demand.extend(table.demand().iter().copied());
+        }
+
+        let mut forbidden = Vec::new();
+        for t in 0..count {
+            for i in 0..self.n {
+                for shipped in 0..count {
+                    let table = &self.periods[shipped];
+                    for j in 0..self.m {
+                        let cell = (t * self.n + i, shipped * self.m + j);
+                        if shipped < t || table.is_forbidden((i, j)) {
+                            forbidden.push(cell);
+                        } else {
+                            costs[cell] = table.costs()[(i, j)] + self.storage(i, shipped - t);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut table = Table::new(costs, Matrix::new_empty(rows, cols), supply, demand);
+        for cell in forbidden {
+            table.forbid(cell);
+        }
+        // supply never shipped over the whole horizon goes to a dummy destination, demand
+        // that no period can meet comes from a dummy source
+        table.balance(Default::default());
+        table
+    }
+
+    pub fn unpack(&self, transport: &Matrix<T>, iterations: usize) -> Schedule<T> {
+        let count = self.periods.len();
+        let mut shipments = vec![Matrix::new_empty(self.n, self.m); count];
+        let mut inventory = vec![vec![T::default(); self.n]; count];
+        let mut unmet = vec![vec![T::default(); self.m]; count];
+        let mut surplus = vec![vec![T::default(); self.n]; count];
+        let mut shipping_cost = T::default();
+        let mut holding_cost = T::default();
+        for t in 0..count {
+            for i in 0..self.n {
+                for shipped in t..count {
+                    for j in 0..self.m {
+                        let quantity = transport[(t * self.n + i, shipped * self.m + j)];
+                        if quantity == Default::default() {
+                            continue;
+                        }
+                        shipments[shipped][(i, j)] += quantity;
+                        // held at the source from the end of period t to period shipped
+                        for stock in inventory[t..shipped].iter_mut() {
+                            stock[i] += quantity;
+                        }
+                        shipping_cost += self.periods[shipped].costs()[(i, j)] * quantity;
+                        holding_cost += self.storage(i, shipped - t) * quantity;
+                    }
+                }
+            }
+        }
+        // the dummy line, if any, is the last row or the last column
+        for t in 0..count {
+            if transport.rows() > count * self.n {
+                for j in 0..self.m {
+                    unmet[t][j] = transport[(count * self.n, t * self.m + j)];
+                }
+            }
+            if transport.cols() > count * self.m {
+                for i in 0..self.n {
+                    surplus[t][i] = transport[(t * self.n + i, count * self.m)];
+                }
+            }
+        }
+        Schedule {
+            shipments,
+            inventory,
+            unmet,
+            surplus,
+            shipping_cost,
+            holding_cost,
+            iterations,
+        }
+    }
+
+    pub fn solve<V>(&self, initializer: Initializer) -> Result<Schedule<T>, SolveError>
+    where
+        V: Signed,
+        T: Into<V>,
+    {
+        let mut table = self.to_table();
+        table.initialize(initializer);
+        let solution = table.optimize::<V>()?;
+        Ok(self.unpack(&solution.transport, solution.iterations))
+    }
+
+    pub fn report(&self, schedule: &Schedule<T>) {
+        for (t, table) in self.periods.iter().enumerate() {
+            println!("Period {} :", t + 1);
+            table.display(&schedule.shipments[t]);
+            for i in 0..self.n {
+                let stock = schedule.inventory[t][i];
+                if stock != Default::default() {
+                    println!("Stock at S{} : {}", i + 1, stock);
+                }
+            }
+            for j in 0..self.m {
+                let flow = schedule.unmet[t][j];
+                if flow != Default::default() {
+                    println!("Unmet demand at D{} : {}", j + 1, flow);
+                }
+            }
+            for i in 0..self.n {
+                let flow = schedule.surplus[t][i];
+                if flow != Default::default() {
+                    println!("Surplus supply at S{} : {}", i + 1, flow);
+                }
+            }
+        }
+        println!("Shipping cost : {}", schedule.shipping_cost);
+        println!("Holding cost : {}", schedule.holding_cost);
+        println!(
+            "Total cost : {}",
+            schedule.shipping_cost + schedule.holding_cost
+        );
+        println!("Iterations : {}", schedule.iterations);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unmet_demand_is_reported() {
+        let mut horizon: MultiPeriod<u32> = MultiPeriod::new(1, 1);
+        let costs = Matrix::new(vec![vec![3]]);
+        horizon.add_period(Table::new(costs, Matrix::new_empty(1, 1), vec![2], vec![5]));
+
+        let schedule = horizon.solve::<i64>(Initializer::NorthWestCorner).unwrap();
+        assert_eq!(schedule.shipments[0][(0, 0)], 2);
+        assert_eq!(schedule.unmet, vec![vec![3]]);
+        assert_eq!(schedule.shipping_cost, 6);
+    }
+}
@@ -0,0 +1,457 @@
+// primal network simplex on index arrays, for problems too large for the Graph based MODI
+// the basis is a strongly feasible spanning tree rooted at an artificial node, stored as
+// parent/pred/direction/depth arrays and a preorder thread; the entering arc is chosen
+// by block search pricing and the leaving arc by the rule that keeps the tree strongly
+// feasible, which rules out cycling on degenerate pivots
+
+use crate::tools::number::{Number, Signed};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    // non-basic at zero flow
+    Lower,
+    Tree,
+    // non-basic at capacity
+    Upper,
+}
+
+// orientation of the arc linking a node to its parent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    // from the node to its parent
+    Up,
+    // from the parent to the node
+    Down,
+}
+
+pub struct NetworkSimplex<T, V> {
+    nodes: usize,
+    // real arcs first, then one artificial arc per node to the root
+    source: Vec<usize>,
+    target: Vec<usize>,
+    cost: Vec<V>,
+    capacity: Vec<Option<T>>,
+    flow: Vec<T>,
+    state: Vec<State>,
+    supply: Vec<T>,
+    demand: Vec<T>,
+
+    // spanning tree, the root is the node at index `nodes`
+    potential: Vec<V>,
+    parent: Vec<usize>,
+    pred: Vec<usize>,
+    direction: Vec<Direction>,
+    depth: Vec<usize>,
+    thread: Vec<usize>,
+    rev_thread: Vec<usize>,
+
+    // pricing
+    block_size: usize,
+    next_arc: usize,
+    // scratch space to rebuild the moved subtree
+    children: Vec<Vec<usize>>,
+}
+
+impl<T, V> NetworkSimplex<T, V>
+where
+    T: Number,
+    V: Signed,
+{
+    pub fn new(supply: Vec<T>, demand: Vec<T>) -> Self {
+        assert_eq!(supply.len(), demand.len());
+        Self {
+            nodes: supply.len(),
+            source: Vec::new(),
+            target: Vec::new(),
+            cost: Vec::new(),
+            capacity: Vec::new(),
+            flow: Vec::new(),
+            state: Vec::new(),
+            supply,
+            demand,
+            potential: Vec::new(),
+            parent: Vec::new(),
+            pred: Vec::new(),
+            direction: Vec::new(),
+            depth: Vec::new(),
+            thread: Vec::new(),
+            rev_thread: Vec::new(),
+            block_size: 0,
+            next_arc: 0,
+            children: Vec::new(),
+        }
+    }
+
+    pub fn add_arc(&mut self, from: usize, to: usize, cost: V, capacity: Option<T>) -> usize {
+        assert!(from < self.nodes && to < self.nodes, "Unknown node");
+        self.source.push(from);
+        self.target.push(to);
+        self.cost.push(cost);
+        self.capacity.push(capacity);
+        self.flow.push(Default::default());
+        self.state.push(State::Lower);
+        self.source.len() - 1
+    }
+
+    pub fn flow(&self, arc: usize) -> T {
+        self.flow[arc]
+    }
+
+    pub fn potential(&self, node: usize) -> V {
+        self.potential[node]
+    }
+
+    pub fn solve(&mut self) -> usize {
+        assert!(
+            self.thread.is_empty(),
+            "The network has already been solved"
+        );
+        let arcs = self.source.len();
+        self.init();
+
+        let mut iterations = 0;
+        while let Some(entering) = self.find_entering_arc(arcs) {
+            let join = self.find_join_node(entering);
+            let (delta, leaving) = self.find_leaving_arc(entering, join);
+            self.change_flow(entering, join, delta, leaving.is_some());
+            if let Some((u_in, v_in, u_out)) = leaving {
+                self.update_tree(entering, u_in, v_in, u_out);
+            }
+            iterations += 1;
+        }
+        iterations
+    }
+
+    pub fn unrouted(&self) -> Vec<usize> {
+        // nodes whose artificial arc still carries flow once the solver stopped
+        let arcs = self.source.len() - self.nodes;
+        (0..self.nodes)
+            .filter(|&node| self.flow[arcs + node] != Default::default())
+            .collect()
+    }
+
+    fn init(&mut self) {
+        // the artificial cost is larger than any path through real arcs
+        let largest = self
+            .cost
+            .iter()
+            .map(|&cost| cost.max(-cost))
+            .max()
+            .unwrap_or_default();
+        let mut artificial = V::default();
+        for _ in 0..=self.nodes {
+            artificial = artificial + largest + 1.into();
+        }
+
+        // star tree: supply nodes send their excess up to the root for free, demand nodes
+        // receive theirs from the root at the artificial cost
+        let root = self.nodes;
+        self.potential = vec![Default::default(); root + 1];
+        self.parent = vec![root; root + 1];
+        self.pred = vec![0; root + 1];
+        self.direction = vec![Direction::Up; root + 1];
+        self.depth = vec![1; root + 1];
+        self.thread = (1..=root + 1).map(|next| next % (root + 1)).collect();
+        self.rev_thread = (0..=root).map(|node| (node + root) % (root + 1)).collect();
+        self.children = vec![Vec::new(); root + 1];
+        self.depth[root] = 0;
+        for node in 0..self.nodes {
+            let (supply, demand) = (self.supply[node], self.demand[node]);
+            let arc = if supply >= demand {
+                let arc = self.add_arc_unchecked(node, root, Default::default());
+                self.flow[arc] = supply - demand;
+                arc
+            } else {
+                let arc = self.add_arc_unchecked(root, node, artificial);
+                self.flow[arc] = demand - supply;
+                self.direction[node] = Direction::Down;
+                self.potential[node] = artificial;
+                arc
+            };
+            self.state[arc] = State::Tree;
+            self.pred[node] = arc;
+        }
+
+        let arcs = self.source.len() - self.nodes;
+        self.block_size = ((arcs as f64).sqrt() as usize).max(10);
+        self.next_arc = 0;
+    }
+
+    fn add_arc_unchecked(&mut self, from: usize, to: usize, cost: V) -> usize {
+        self.source.push(from);
+        self.target.push(to);
+        self.cost.push(cost);
+        self.capacity.push(None);
+        self.flow.push(Default::default());
+        self.state.push(State::Lower);
+        self.source.len() - 1
+    }
+
+    fn reduced_cost(&self, arc: usize) -> V {
+        self.cost[arc] + self.potential[self.source[arc]] - self.potential[self.target[arc]]
+    }
+
+    fn find_entering_arc(&mut self, arcs: usize) -> Option<usize> {
+        // scan the real arcs in blocks from where the last search stopped, and take the
+        // most violating arc of the first block holding one
+        if arcs == 0 {
+            return None;
+        }
+        let mut best: Option<(V, usize)> = None;
+        let mut count = self.block_size;
+        let mut k = 0;
+        while k < arcs {
+            let arc = (self.next_arc + k) % arcs;
+            let violation = match self.state[arc] {
+                State::Lower => self.reduced_cost(arc),
+                State::Upper => -self.reduced_cost(arc),
+                State::Tree => V::default(),
+            };
+            if violation < V::default() && best.is_none_or(|(min, _)| violation < min) {
+                best = Some((violation, arc));
+            }
+            k += 1;
+            count -= 1;
+            if count == 0 {
+                if best.is_some() {
+                    break;
+                }
+                count = self.block_size;
+            }
+        }
+        self.next_arc = (self.next_arc + k) % arcs;
+        best.map(|(_, arc)| arc)
+    }
+
+    fn find_join_node(&self, arc: usize) -> usize {
+        let (mut u, mut v) = (self.source[arc], self.target[arc]);
+        while u != v {
+            if self.depth[u] >= self.depth[v] {
+                u = self.parent[u];
+            } else {
+                v = self.parent[v];
+            }
+        }
+        u
+    }
+
+    // ends of the cycle: flow goes from the join node down to `first`, through the entering
+    // arc, and from `second` back up to the join node
+    fn cycle_ends(&self, arc: usize) -> (usize, usize) {
+        match self.state[arc] {
+            State::Lower => (self.source[arc], self.target[arc]),
+            _ => (self.target[arc], self.source[arc]),
+        }
+    }
+
+    fn room(&self, node: usize, increase: bool) -> Option<T> {
+        let arc = self.pred[node];
+        if increase {
+            self.capacity[arc].map(|capacity| capacity - self.flow[arc])
+        } else {
+            Some(self.flow[arc])
+        }
+    }
+
+    fn find_leaving_arc(&self, entering: usize, join: usize) -> (T, Option<(usize, usize, usize)>) {
+        // the last blocking arc met when going around the cycle from the join node leaves,
+        // hence `<` on the first side and `<=` on the second one
+        let (first, second) = self.cycle_ends(entering);
+        let mut delta = self.capacity[entering];
+        let mut result = None;
+
+        let mut node = first;
+        while node != join {
+            let room = self.room(node, self.direction[node] == Direction::Down);
+            if let Some(room) = room {
+                if delta.is_none_or(|delta| room < delta) {
+                    delta = Some(room);
+                    result = Some((first, second, node));
+                }
+            }
+            node = self.parent[node];
+        }
+        let mut node = second;
+        while node != join {
+            let room = self.room(node, self.direction[node] == Direction::Up);
+            if let Some(room) = room {
+                if delta.is_none_or(|delta| room <= delta) {
+                    delta = Some(room);
+                    result = Some((second, first, node));
+                }
+            }
+            node = self.parent[node];
+        }
+
+        let delta = delta.expect("The problem is unbounded");
+        (delta, result)
+    }
+
+    fn change_flow(&mut self, entering: usize, join: usize, delta: T, change: bool) {
+        let (first, second) = self.cycle_ends(entering);
+        if delta != Default::default() {
+            if self.state[entering] == State::Lower {
+                self.flow[entering] += delta;
+            } else {
+                self.flow[entering] -= delta;
+            }
+            for (start, increase) in [(first, Direction::Down), (second, Direction::Up)] {
+                let mut node = start;
+                while node != join {
+                    let arc = self.pred[node];
+                    if self.direction[node] == increase {
+                        self.flow[arc] += delta;
+                    } else {
+                        self.flow[arc] -= delta;
+                    }
+                    node = self.parent[node];
+                }
+            }
+        }
+
+        if !change {
+            // the entering arc went from one bound to the other
+            self.state[entering] = match self.state[entering] {
+                State::Lower => State::Upper,
+                _ => State::Lower,
+            };
+        }
+    }
+
+    fn update_tree(&mut self, entering: usize, u_in: usize, v_in: usize, u_out: usize) {
+        let leaving = self.pred[u_out];
+        self.state[leaving] = if self.flow[leaving] == Default::default() {
+            State::Lower
+        } else {
+            State::Upper
+        };
+        self.state[entering] = State::Tree;
+
+        // cut the subtree of u_out out of the thread, it is the block following u_out
+        // whose nodes are deeper than u_out
+        let mut subtree = vec![u_out];
+        let mut last = u_out;
+        while self.depth[self.thread[last]] > self.depth[u_out] {
+            last = self.thread[last];
+            subtree.push(last);
+        }
+        let (before, after) = (self.rev_thread[u_out], self.thread[last]);
+        self.thread[before] = after;
+        self.rev_thread[after] = before;
+
+        // reverse the stem from u_in up to u_out, u_in now hangs from v_in
+        let mut node = u_in;
+        let mut parent = v_in;
+        let mut pred = entering;
+        let mut direction = if self.source[entering] == u_in {
+            Direction::Up
+        } else {
+            Direction::Down
+        };
+        loop {
+            let next = self.parent[node];
+            let next_pred = self.pred[node];
+            let next_direction = match self.direction[node] {
+                Direction::Up => Direction::Down,
+                Direction::Down => Direction::Up,
+            };
+            self.parent[node] = parent;
+            self.pred[node] = pred;
+            self.direction[node] = direction;
+            if node == u_out {
+                break;
+            }
+            parent = node;
+            pred = next_pred;
+            direction = next_direction;
+            node = next;
+        }
+
+        // the whole subtree moves by the same amount so that the entering arc gets a
+        // zero reduced cost
+        let sigma = match self.direction[u_in] {
+            Direction::Up => self.potential[v_in] - self.cost[entering] - self.potential[u_in],
+            Direction::Down => self.potential[v_in] + self.cost[entering] - self.potential[u_in],
+        };
+
+        // walk the subtree again from u_in in preorder to rebuild its thread and depths,
+        // then splice it right after v_in
+        for &node in subtree.iter() {
+            if node != u_in {
+                self.children[self.parent[node]].push(node);
+            }
+        }
+        let mut order = Vec::with_capacity(subtree.len());
+        let mut stack = vec![u_in];
+        while let Some(node) = stack.pop() {
+            self.depth[node] = self.depth[self.parent[node]] + 1;
+            self.potential[node] = self.potential[node] + sigma;
+            order.push(node);
+            stack.append(&mut self.children[node]);
+        }
+
+        let after = self.thread[v_in];
+        let mut previous = v_in;
+        for &node in order.iter() {
+            self.thread[previous] = node;
+            self.rev_thread[node] = previous;
+            previous = node;
+        }
+        self.thread[previous] = after;
+        self.rev_thread[after] = previous;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::initial::Initializer;
+    use crate::tools::table::{Objective, Table};
+
+    #[test]
+    fn agrees_with_modi_on_the_data_files() {
+        let mut files: Vec<String> = std::fs::read_dir("data")
+            .unwrap()
+            .map(|entry| entry.unwrap().path().to_string_lossy().into_owned())
+            .collect();
+        files.sort();
+        // forbidden cells and capacities
+        assert!(files.iter().any(|file| file.ends_with("/13.txt")));
+
+        for file in files.iter() {
+            for objective in [Objective::Minimize, Objective::Maximize] {
+                let mut table: Table<u32> = Table::from_file(file).unwrap();
+                table.set_objective(objective);
+                let simplex = table.network_simplex::<i64>().unwrap();
+                table.initialize(Initializer::Russell);
+                let modi = table.optimize::<i64>().unwrap();
+                assert_eq!(
+                    simplex.total_cost, modi.total_cost,
+                    "{} ({:?})",
+                    file, objective
+                );
+
+                // the plan ships every supply and meets every demand within the capacities
+                let transport = &simplex.transport;
+                table.assert_ships_everything(transport, file);
+                for i in 0..transport.rows() {
+                    for j in 0..transport.cols() {
+                        let flow = transport[(i, j)];
+                        assert!(!table.is_forbidden((i, j)) || flow == 0, "{}", file);
+                        assert!(table.capacity()[(i, j)].is_none_or(|cap| flow <= cap));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn capacities_leave_supply_unrouted() {
+        // node 0 ships 5 to node 1 through a single arc of capacity 3
+        let mut network: NetworkSimplex<u32, i64> = NetworkSimplex::new(vec![5, 0], vec![0, 5]);
+        let arc = network.add_arc(0, 1, 2, Some(3));
+        network.solve();
+        assert_eq!(network.flow(arc), 3);
+        assert_eq!(network.unrouted(), vec![0, 1]);
+    }
+}
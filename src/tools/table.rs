@@ -1,8 +1,6 @@
 use std::{
+    collections::{HashSet, VecDeque},
     fmt::{Debug, Display},
-    iter::Sum,
-    num::ParseIntError,
-    ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign},
     str::FromStr,
     vec,
 };
@@ -11,14 +9,228 @@ use tabled::{
     settings::{Alignment, Style},
 };
 
-use crate::tools::graph::Graph;
+use crate::tools::error::{ParseError, ParseErrorKind, SolveError};
+use crate::tools::flow::FlowNetwork;
+use crate::tools::graph::{Degeneracy, Graph, Sign};
+use crate::tools::hungarian::hungarian;
+use crate::tools::initial::{
+    BalasHammer, ColumnMinimum, InitialSolution, Initializer, LeastCost, NorthWestCorner,
+    RowMinimum, Russell,
+};
 use crate::tools::matrix::Matrix;
+use crate::tools::number::{Number, Signed};
+use crate::tools::simplex::NetworkSimplex;
 
 use super::graph::Edge;
 
+// cells of a stepping-stone cycle with the side they are on
+type Cycle = Vec<((usize, usize), Sign)>;
+
+// reads a data file line by line, keeping the positions for error reporting
+struct Reader<'a> {
+    filename: &'a str,
+    lines: std::iter::Enumerate<std::str::Lines<'a>>,
+    expected: usize,
+    read: usize,
+}
+
+struct Line<'a> {
+    filename: &'a str,
+    number: usize,
+    tokens: Vec<&'a str>,
+}
+
+impl<'a> Reader<'a> {
+    fn new(filename: &'a str, file: &'a str) -> Self {
+        Self {
+            filename,
+            lines: file.lines().enumerate(),
+            expected: 1,
+            read: 0,
+        }
+    }
+
+    fn expect_lines(&mut self, expected: usize) {
+        self.expected = expected;
+    }
+
+    fn next_line(&mut self) -> Result<Line<'a>, ParseError> {
+        match self.lines.next() {
+            Some((number, line)) => {
+                self.read += 1;
+                Ok(Line {
+                    filename: self.filename,
+                    number: number + 1,
+                    tokens: line.split_whitespace().collect(),
+                })
+            }
+            None => Err(ParseError::new(
+                self.filename,
+                self.read + 1,
+                0,
+                ParseErrorKind::WrongDimensions {
+                    expected: self.expected,
+                    found: self.read,
+                },
+            )),
+        }
+    }
+
+    fn remaining(&self) -> usize {
+        // non-blank lines left to read, without consuming them
+        self.lines
+            .clone()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .count()
+    }
+
+    fn width(&self) -> usize {
+        // tokens on the next line, without consuming it
+        self.lines
+            .clone()
+            .next()
+            .map_or(0, |(_, line)| line.split_whitespace().count())
+    }
+
+    fn end(&mut self) -> Result<(), ParseError> {
+        // blank lines at the end of the file are fine
+        for (number, line) in self.lines.by_ref() {
+            if let Some(token) = line.split_whitespace().next() {
+                return Err(ParseError::new(
+                    self.filename,
+                    number + 1,
+                    1,
+                    ParseErrorKind::TrailingData(token.to_string()),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Line<'_> {
+    fn token(&self, k: usize, expected: &'static str) -> Result<&str, ParseError> {
+        self.tokens.get(k).copied().ok_or_else(|| {
+            ParseError::new(
+                self.filename,
+                self.number,
+                k + 1,
+                ParseErrorKind::MissingToken(expected),
+            )
+        })
+    }
+
+    fn parse<U: FromStr>(&self, k: usize, expected: &'static str) -> Result<U, ParseError> {
+        let token = self.token(k, expected)?;
+        token.parse().map_err(|_| {
+            ParseError::new(
+                self.filename,
+                self.number,
+                k + 1,
+                ParseErrorKind::NotANumber(token.to_string()),
+            )
+        })
+    }
+
+    fn end(&self, k: usize) -> Result<(), ParseError> {
+        match self.tokens.get(k) {
+            Some(token) => Err(ParseError::new(
+                self.filename,
+                self.number,
+                k + 1,
+                ParseErrorKind::TrailingData(token.to_string()),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+pub struct Solution<T> {
+    pub transport: Matrix<T>,
+    pub total_cost: T,
+    pub iterations: usize,
+}
+
+// what to do with a file whose total supply and total demand differ
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Balancing<T> {
+    Reject,
+    // append a dummy source or destination whose cells all cost the given value
+    Dummy(T),
+}
+
+// the line added to balance the problem: flows from a dummy source are unmet demand,
+// flows to a dummy destination are supply left at the source
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dummy {
+    Source,
+    Destination,
+}
+
+// whether the matrix holds costs to minimize or profits to maximize
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Objective {
+    #[default]
+    Minimize,
+    Maximize,
+}
+
+// plan minimizing the longest time used, costs being read as times
+pub struct Bottleneck<T> {
+    pub transport: Matrix<T>,
+    pub time: T,
+    pub total_cost: T,
+}
+
+// plan trading the cost per unit against the setup cost of the routes it opens
+pub struct FixedCharge<T> {
+    pub transport: Matrix<T>,
+    pub variable_cost: T,
+    pub fixed_cost: T,
+    pub iterations: usize,
+}
+
+// range a cost, a supply or a demand can take while the optimal basis stays optimal and
+// feasible, None when unbounded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Interval<V> {
+    pub lower: Option<V>,
+    pub upper: Option<V>,
+}
+
+impl<V: Display> Display for Interval<V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.lower {
+            Some(lower) => write!(f, "[{}", lower)?,
+            None => write!(f, "(-inf")?,
+        }
+        match &self.upper {
+            Some(upper) => write!(f, ", {}]", upper),
+            None => write!(f, ", +inf)"),
+        }
+    }
+}
+
+// dual price of a supply or a demand: what one more unit costs (or earns) when the change
+// is balanced at S1, and the amounts over which that price holds
+pub struct ShadowPrice<V> {
+    pub price: V,
+    pub range: Interval<V>,
+}
+
+#[derive(Clone)]
 pub struct Table<T> {
     costs: Matrix<T>,
+    objective: Objective,
     transport: Matrix<T>,
+    basis: Vec<(usize, usize)>,
+    degeneracy: Degeneracy,
+    dummy: Option<Dummy>,
+    forbidden: Matrix<bool>,
+    // upper bound on the flow of each route, None when unbounded
+    capacity: Matrix<Option<T>>,
+    // cost paid once for every route carrying flow
+    fixed: Matrix<T>,
     supply: Vec<T>,
     demand: Vec<T>,
     n: usize,
@@ -27,26 +239,7 @@ pub struct Table<T> {
 
 impl<T> Table<T>
 where
-    T: Default
-        + Display
-        + Clone
-        + Copy
-        + PartialEq
-        + PartialOrd
-        + AddAssign
-        + Debug
-        + From<u8>
-        + Add<Output = T>
-        + Mul<Output = T>
-        + Sub<Output = T>
-        + FromStr<Err = ParseIntError>
-        + Ord
-        + SubAssign
-        + Copy
-        + Sum
-        + PartialOrd
-        + Div<Output = T>
-        + SubAssign,
+    T: Number,
 {
     pub fn new(costs: Matrix<T>, transport: Matrix<T>, supply: Vec<T>, demand: Vec<T>) -> Self {
         // Check if the number of rows in the costs matrix is equal to the length of the supply vector
@@ -61,9 +254,26 @@ where
         let n = supply.len();
         let m = demand.len();
 
+        // without an initializer the basis can only be inferred from the shipped cells
+        let mut basis = Vec::new();
+        for i in 0..n {
+            for j in 0..m {
+                if transport[(i, j)] != Default::default() {
+                    basis.push((i, j));
+                }
+            }
+        }
+
         Self {
             costs,
+            objective: Objective::default(),
             transport,
+            basis,
+            degeneracy: Degeneracy::default(),
+            dummy: None,
+            forbidden: Matrix::new_empty(n, m),
+            capacity: Matrix::new_empty(n, m),
+            fixed: Matrix::new_empty(n, m),
             supply,
             demand,
             n,
@@ -74,7 +284,14 @@ where
     fn new_empty(n: usize, m: usize) -> Self {
         Self {
             costs: Matrix::new_empty(n, m),
+            objective: Objective::default(),
             transport: Matrix::new_empty(n, m),
+            basis: Vec::new(),
+            degeneracy: Degeneracy::default(),
+            dummy: None,
+            forbidden: Matrix::new_empty(n, m),
+            capacity: Matrix::new_empty(n, m),
+            fixed: Matrix::new_empty(n, m),
             supply: vec![Default::default(); n],
             demand: vec![Default::default(); m],
             n,
@@ -82,52 +299,355 @@ where
         }
     }
 
-    pub fn from_file(filename: &str) -> Self {
+    pub fn from_file(filename: &str) -> Result<Self, ParseError> {
+        Self::from_file_with(filename, Balancing::Reject)
+    }
+
+    pub fn from_file_with(filename: &str, balancing: Balancing<T>) -> Result<Self, ParseError> {
         // file structure:
         // n m
-        // c11 c12 ... c1m d1
-        // c21 c22 ... c2m d2
+        // c11 c12 ... c1m s1
+        // c21 c22 ... c2m s2
         // ...
-        // cn1 cn2 ... cnm dn
-        // s1 s2 ... sm
-        let file = std::fs::read_to_string(filename).unwrap();
-        let mut lines = file.lines();
-        let mut nm = lines.next().unwrap().split_whitespace();
-        let n = nm.next().unwrap().parse().unwrap();
-        let m = nm.next().unwrap().parse().unwrap();
-        println!("n: {}, m: {}", n, m);
+        // cn1 cn2 ... cnm sn
+        // d1 d2 ... dm
+        // [u11 u12 ... u1m]
+        // [...]
+        // [un1 un2 ... unm]
+        // a cost written `-` or `X` marks a forbidden route, the optional last n lines give
+        // the capacity of each route, `-` or `inf` leaving it unbounded
+        let file = std::fs::read_to_string(filename)
+            .map_err(|e| ParseError::new(filename, 0, 0, ParseErrorKind::Io(e.to_string())))?;
+        let mut reader = Reader::new(filename, &file);
+
+        let header = reader.next_line()?;
+        let n: usize = header.parse(0, "number of sources")?;
+        let m: usize = header.parse(1, "number of destinations")?;
+        header.end(2)?;
+        reader.expect_lines(n + 2);
+
+        // the dimensions are checked against what the file holds before the matrices are
+        // allocated, a typo in the header would otherwise exhaust the memory
+        let remaining = reader.remaining();
+        if remaining < n + 1 {
+            return Err(ParseError::new(
+                filename,
+                reader.read + remaining + 1,
+                0,
+                ParseErrorKind::WrongDimensions {
+                    expected: n + 2,
+                    found: reader.read + remaining,
+                },
+            ));
+        }
+        let width = reader.width();
+        if n > 0 && width < m + 1 {
+            let expected = if width < m { "cost" } else { "supply" };
+            return Err(ParseError::new(
+                filename,
+                reader.read + 1,
+                width + 1,
+                ParseErrorKind::MissingToken(expected),
+            ));
+        }
+
         let mut costs = Matrix::new_empty(n, m);
+        let mut forbidden = Matrix::new_empty(n, m);
         let mut supply: Vec<T> = vec![Default::default(); n];
-        let mut demand: Vec<T> = vec![Default::default(); m];
         for i in 0..n {
-            let mut line = lines.next().unwrap().split_whitespace();
+            let line = reader.next_line()?;
             for j in 0..m {
-                costs[(i, j)] = line.next().unwrap().parse().unwrap();
+                match line.token(j, "cost")? {
+                    "-" | "X" => forbidden[(i, j)] = true,
+                    _ => costs[(i, j)] = line.parse(j, "cost")?,
+                }
             }
-            supply[i] = line.next().unwrap().parse().unwrap();
+            supply[i] = line.parse(m, "supply")?;
+            line.end(m + 1)?;
         }
-        let mut line = lines.next().unwrap().split_whitespace();
-        for i in 0..m {
-            demand[i] = line.next().unwrap().parse().unwrap();
+        let line = reader.next_line()?;
+        let demand = (0..m)
+            .map(|j| line.parse(j, "demand"))
+            .collect::<Result<Vec<T>, _>>()?;
+        line.end(m)?;
+        let demand_line = line.number;
+
+        // a capacity block is only read when a full line per source follows, anything
+        // shorter is trailing data
+        let mut capacity = Matrix::new_empty(n, m);
+        if n > 0 && reader.remaining() >= n {
+            reader.expect_lines(2 * n + 2);
+            for i in 0..n {
+                let line = reader.next_line()?;
+                for j in 0..m {
+                    capacity[(i, j)] = match line.token(j, "capacity")? {
+                        "-" | "inf" => None,
+                        _ => Some(line.parse(j, "capacity")?),
+                    };
+                }
+                line.end(m)?;
+            }
         }
         // Check if there are no more lines in the file
-        assert!(lines.next().is_none());
+        reader.end()?;
+
         // Check if the sum of the supply vector is equal to the sum of the demand vector
-        assert!(
-            supply.iter().copied().sum::<T>() == demand.iter().copied().sum::<T>(),
-            "Supply and demand are not balanced"
-        );
-        Self::new(costs, Matrix::new_empty(n, m), supply, demand)
+        let total_supply = supply.iter().copied().sum::<T>();
+        let total_demand = demand.iter().copied().sum::<T>();
+        let mut table = Self::new(costs, Matrix::new_empty(n, m), supply, demand);
+        table.forbidden = forbidden;
+        table.capacity = capacity;
+        if let Balancing::Dummy(cost) = balancing {
+            table.balance(cost);
+        } else if total_supply != total_demand {
+            return Err(ParseError::new(
+                filename,
+                demand_line,
+                0,
+                ParseErrorKind::Unbalanced {
+                    supply: total_supply.to_string(),
+                    demand: total_demand.to_string(),
+                },
+            ));
+        }
+        Ok(table)
+    }
+
+    pub fn balance(&mut self, cost: T) -> Option<Dummy> {
+        // the dummy line absorbs the difference between total supply and total demand,
+        // any previous plan is dropped since the table changes shape
+        let total_supply = self.supply.iter().copied().sum::<T>();
+        let total_demand = self.demand.iter().copied().sum::<T>();
+        if total_supply == total_demand || self.dummy.is_some() {
+            return None;
+        }
+
+        if total_supply > total_demand {
+            self.costs.push_col(vec![cost; self.n]);
+            self.forbidden.push_col(vec![false; self.n]);
+            self.capacity.push_col(vec![None; self.n]);
+            self.fixed.push_col(vec![Default::default(); self.n]);
+            self.demand.push(total_supply - total_demand);
+            self.m += 1;
+            self.dummy = Some(Dummy::Destination);
+        } else {
+            self.costs.push_row(vec![cost; self.m]);
+            self.forbidden.push_row(vec![false; self.m]);
+            self.capacity.push_row(vec![None; self.m]);
+            self.fixed.push_row(vec![Default::default(); self.m]);
+            self.supply.push(total_demand - total_supply);
+            self.n += 1;
+            self.dummy = Some(Dummy::Source);
+        }
+        self.transport = Matrix::new_empty(self.n, self.m);
+        self.basis.clear();
+        self.dummy
+    }
+
+    pub fn dummy(&self) -> Option<Dummy> {
+        self.dummy
+    }
+
+    fn source_label(&self, i: usize) -> String {
+        if self.dummy == Some(Dummy::Source) && i == self.n - 1 {
+            format!("S{} (dummy)", i + 1)
+        } else {
+            format!("S{}", i + 1)
+        }
+    }
+
+    fn destination_label(&self, j: usize) -> String {
+        if self.dummy == Some(Dummy::Destination) && j == self.m - 1 {
+            format!("D{} (dummy)", j + 1)
+        } else {
+            format!("D{}", j + 1)
+        }
     }
 
     pub fn costs(&self) -> &Matrix<T> {
         &self.costs
     }
 
+    pub fn set_costs(&mut self, costs: Matrix<T>) {
+        assert_eq!((costs.rows(), costs.cols()), (self.n, self.m));
+        self.costs = costs;
+    }
+
     pub fn transport(&self) -> &Matrix<T> {
         &self.transport
     }
 
+    pub fn basis(&self) -> &Vec<(usize, usize)> {
+        &self.basis
+    }
+
+    pub fn degeneracy(&self) -> Degeneracy {
+        self.degeneracy
+    }
+
+    pub fn set_degeneracy(&mut self, degeneracy: Degeneracy) {
+        self.degeneracy = degeneracy;
+    }
+
+    pub fn forbidden(&self) -> &Matrix<bool> {
+        &self.forbidden
+    }
+
+    pub fn forbid(&mut self, (i, j): (usize, usize)) {
+        self.forbidden[(i, j)] = true;
+    }
+
+    pub fn is_forbidden(&self, (i, j): (usize, usize)) -> bool {
+        self.forbidden[(i, j)]
+    }
+
+    pub fn capacity(&self) -> &Matrix<Option<T>> {
+        &self.capacity
+    }
+
+    pub fn set_capacity(&mut self, (i, j): (usize, usize), capacity: Option<T>) {
+        self.capacity[(i, j)] = capacity;
+    }
+
+    pub fn is_capacitated(&self) -> bool {
+        self.capacity.data().iter().flatten().any(Option::is_some)
+    }
+
+    fn exceeds_capacity(&self) -> bool {
+        (0..self.n)
+            .flat_map(|i| (0..self.m).map(move |j| (i, j)))
+            .any(|cell| self.capacity[cell].is_some_and(|cap| self.transport[cell] > cap))
+    }
+
+    fn at_capacity(&self, (i, j): (usize, usize)) -> bool {
+        self.capacity[(i, j)]
+            .is_some_and(|cap| cap != Default::default() && self.transport[(i, j)] == cap)
+    }
+
+    fn can_enter(&self, cell: (usize, usize)) -> bool {
+        // a cell with no room never moves flow, and a forbidden cell only enters to be
+        // emptied when it sits at its capacity
+        let forbidden = self.forbidden[cell] && !self.at_capacity(cell);
+        !forbidden && self.capacity[cell] != Some(Default::default())
+    }
+
+    pub fn fixed_costs(&self) -> &Matrix<T> {
+        &self.fixed
+    }
+
+    pub fn set_fixed_costs(&mut self, fixed: Matrix<T>) {
+        assert_eq!((fixed.rows(), fixed.cols()), (self.n, self.m));
+        self.fixed = fixed;
+    }
+
+    pub fn fixed_cost_of(&self, transport: &Matrix<T>) -> T {
+        (0..self.n)
+            .flat_map(|i| (0..self.m).map(move |j| (i, j)))
+            .filter(|&cell| transport[cell] != Default::default())
+            .fold(Default::default(), |acc, cell| acc + self.fixed[cell])
+    }
+
+    pub fn objective(&self) -> Objective {
+        self.objective
+    }
+
+    pub fn set_objective(&mut self, objective: Objective) {
+        self.objective = objective;
+    }
+
+    fn max_cost(&self) -> T {
+        (0..self.n)
+            .flat_map(|i| (0..self.m).map(move |j| (i, j)))
+            .filter(|&cell| !self.forbidden[cell])
+            .map(|cell| self.costs[cell])
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn big_m<V>(&self) -> V
+    where
+        V: Signed,
+        T: Into<V>,
+    {
+        // any plan avoiding the forbidden cells costs (or earns) at most max_cost * total_supply,
+        // so a single unit through a forbidden cell at big-M is always worse. the product
+        // easily outgrows the input type, it is only ever computed in the solver type
+        let total_supply = self
+            .supply
+            .iter()
+            .fold(V::default(), |acc, &supply| acc + supply.into());
+        self.max_cost().into() * total_supply + 1.into()
+    }
+
+    pub fn working_costs(&self) -> Matrix<T> {
+        // the costs to minimize seen by the heuristics: forbidden cells are priced just above
+        // every allowed cell so that they are ranked last, and profits are turned into regrets
+        // max_profit - p_ij, which keeps them unsigned
+        let has_forbidden = self
+            .forbidden
+            .data()
+            .iter()
+            .flatten()
+            .any(|&forbidden| forbidden);
+        if self.objective == Objective::Minimize && !has_forbidden {
+            return self.costs.clone();
+        }
+        let max_cost = self.max_cost();
+        let mut costs = Matrix::new_empty(self.n, self.m);
+        for i in 0..self.n {
+            for j in 0..self.m {
+                costs[(i, j)] = match (self.forbidden[(i, j)], self.objective) {
+                    (true, _) => max_cost + 1.into(),
+                    (false, Objective::Minimize) => self.costs[(i, j)],
+                    (false, Objective::Maximize) => max_cost - self.costs[(i, j)],
+                };
+            }
+        }
+        costs
+    }
+
+    fn solver_costs<V>(&self) -> Matrix<V>
+    where
+        V: Signed,
+        T: Into<V>,
+    {
+        // the costs (or profits) used by the potentials, forbidden cells are at +big-M
+        // when minimizing and at -big-M when maximizing
+        let big_m = self.big_m_for((0..self.n).flat_map(|i| (0..self.m).map(move |j| (i, j))));
+        let mut costs = Matrix::new_empty(self.n, self.m);
+        for i in 0..self.n {
+            for j in 0..self.m {
+                costs[(i, j)] = self.solver_cost((i, j), big_m);
+            }
+        }
+        costs
+    }
+
+    fn solver_cost<V>(&self, (i, j): (usize, usize), big_m: V) -> V
+    where
+        V: Signed,
+        T: Into<V>,
+    {
+        match (self.forbidden[(i, j)], self.objective) {
+            (false, _) => self.costs[(i, j)].into(),
+            (true, Objective::Minimize) => big_m,
+            (true, Objective::Maximize) => -big_m,
+        }
+    }
+
+    fn big_m_for<V>(&self, mut cells: impl Iterator<Item = (usize, usize)>) -> V
+    where
+        V: Signed,
+        T: Into<V>,
+    {
+        // big-M scans every cost, it is only computed when one of the cells is forbidden
+        if cells.any(|cell| self.forbidden[cell]) {
+            self.big_m()
+        } else {
+            V::default()
+        }
+    }
+
     pub fn supply(&self) -> &Vec<T> {
         &self.supply
     }
@@ -136,7 +656,24 @@ where
         &self.demand
     }
 
+    #[cfg(test)]
+    pub fn assert_ships_everything(&self, transport: &Matrix<T>, context: &str) {
+        // the plan ships every supply and meets every demand
+        for (i, &supply) in self.supply.iter().enumerate() {
+            let shipped: T = (0..self.m).map(|j| transport[(i, j)]).sum();
+            assert_eq!(shipped, supply, "{} S{}", context, i + 1);
+        }
+        for (j, &demand) in self.demand.iter().enumerate() {
+            let received: T = (0..self.n).map(|i| transport[(i, j)]).sum();
+            assert_eq!(received, demand, "{} D{}", context, j + 1);
+        }
+    }
+
     pub fn total_cost(&self) -> T {
+        self.cost_of(&self.transport)
+    }
+
+    pub fn cost_of(&self, transport: &Matrix<T>) -> T {
         self.costs
             .data()
             .iter()
@@ -144,60 +681,146 @@ where
             .fold(Default::default(), |acc, (i, row)| {
                 row.iter()
                     .enumerate()
-                    .fold(acc, |acc, (j, &cost)| acc + cost * self.transport[(i, j)])
+                    .fold(acc, |acc, (j, &cost)| acc + cost * transport[(i, j)])
             })
     }
 
-    pub fn north_west_corner(&mut self) {
-        let mut i = 0;
-        let mut j = 0;
-        let mut supply = self.supply.clone();
-        let mut demand = self.demand.clone();
-        while i < self.n && j < self.m {
-            let min = std::cmp::min(supply[i], demand[j]);
-            self.transport[(i, j)] = min;
-            supply[i] -= min;
-            demand[j] -= min;
-            if supply[i] == Default::default() {
-                i += 1;
+    pub fn initialize_with(&mut self, heuristic: &dyn InitialSolution<T>) -> T {
+        // the heuristics rank the forbidden cells last but can still be cornered into them,
+        // the flow they leave there is then moved to allowed cells when it can be
+        let costs = self.working_costs();
+        let (transport, basis) = heuristic.solve(&costs, &self.supply, &self.demand);
+        self.transport = transport;
+        self.basis = basis;
+        if self
+            .forbidden
+            .data()
+            .iter()
+            .flatten()
+            .any(|&forbidden| forbidden)
+        {
+            self.clear_forbidden();
+        }
+        self.total_cost()
+    }
+
+    fn clear_forbidden(&mut self) {
+        // stepping-stone on the phase-1 costs, 1 on a forbidden cell and 0 elsewhere, so
+        // that only the forbidden flow is minimized. the potentials then all lie within
+        // n + m of u_1, which starts at n + m to keep them in the (possibly unsigned) input
+        // type; the capacities are left to `optimize`, like for every heuristic
+        let capacity = std::mem::replace(&mut self.capacity, Matrix::new_empty(self.n, self.m));
+        let mut graph = self.complete_basis();
+        let phase_cost = |forbidden: bool| -> T { (forbidden as u8).into() };
+        let offset = (0..self.n + self.m).fold(T::default(), |acc, _| acc + 1.into());
+        loop {
+            let mut u = vec![T::default(); self.n];
+            let mut v = vec![T::default(); self.m];
+            let mut visited = vec![false; self.n + self.m];
+            u[0] = offset;
+            visited[0] = true;
+            let mut queue = VecDeque::from([0]);
+            while let Some(node) = queue.pop_front() {
+                for &(i, j) in self.basis.iter() {
+                    let next = if node < self.n && i == node {
+                        self.n + j
+                    } else if node >= self.n && j == node - self.n {
+                        i
+                    } else {
+                        continue;
+                    };
+                    if visited[next] {
+                        continue;
+                    }
+                    let cost = phase_cost(self.forbidden[(i, j)]);
+                    if node < self.n {
+                        v[j] = u[i] - cost;
+                    } else {
+                        u[i] = cost + v[j];
+                    }
+                    visited[next] = true;
+                    queue.push_back(next);
+                }
             }
-            if demand[j] == Default::default() {
-                j += 1;
+
+            // the cell whose marginal cost c_ij - u_i + v_j is the most negative enters
+            let mut best: Option<(T, (usize, usize))> = None;
+            for (i, &u_i) in u.iter().enumerate() {
+                for (j, &v_j) in v.iter().enumerate() {
+                    let cost = phase_cost(self.forbidden[(i, j)]) + v_j;
+                    if cost < u_i && best.is_none_or(|(max, _)| u_i - cost > max) {
+                        best = Some((u_i - cost, (i, j)));
+                    }
+                }
+            }
+            match best {
+                Some((_, cell)) => {
+                    self.pivot(&mut graph, cell, true);
+                }
+                None => break,
             }
         }
+        self.capacity = capacity;
+    }
+
+    pub fn initialize(&mut self, initializer: Initializer) -> T {
+        self.initialize_with(&initializer)
+    }
+
+    pub fn north_west_corner(&mut self) {
+        self.initialize_with(&NorthWestCorner);
+    }
+
+    pub fn balas_hammer(&mut self) {
+        self.initialize_with(&BalasHammer);
+    }
+
+    pub fn least_cost(&mut self) {
+        self.initialize_with(&LeastCost);
+    }
+
+    pub fn row_minimum(&mut self) {
+        self.initialize_with(&RowMinimum);
+    }
+
+    pub fn column_minimum(&mut self) {
+        self.initialize_with(&ColumnMinimum);
+    }
+
+    pub fn russell(&mut self) {
+        self.initialize_with(&Russell);
     }
 
     pub fn get_graph(&self) -> Graph<T> {
         let mut graph = Graph::new();
+        graph.set_degeneracy(self.degeneracy);
         for i in 0..self.n {
             graph.add_node(format!("S{}", i + 1));
         }
         for j in 0..self.m {
             graph.add_node(format!("D{}", j + 1));
         }
-        for i in 0..self.n {
-            for j in 0..self.m {
-                if self.transport[(i, j)] != Default::default() {
-                    graph.add_edge(
-                        format!("S{}", i + 1),
-                        format!("D{}", j + 1),
-                        self.transport[(i, j)],
-                    );
-                }
-            }
+        for &(i, j) in self.basis.iter() {
+            graph.add_edge(
+                format!("S{}", i + 1),
+                format!("D{}", j + 1),
+                self.transport[(i, j)],
+            );
         }
         graph
     }
 
     pub fn get_unused_edges(&self) -> Vec<Edge<T>> {
+        let basis: HashSet<&(usize, usize)> = self.basis.iter().collect();
+        let costs = self.working_costs();
         let mut unused = Vec::new();
         for i in 0..self.n {
             for j in 0..self.m {
-                if self.transport[(i, j)] == Default::default() {
+                if !basis.contains(&(i, j)) {
                     unused.push(Edge::new(
                         format!("S{}", i + 1),
                         format!("D{}", j + 1),
-                        self.costs[(i, j)],
+                        costs[(i, j)],
                     ));
                 }
             }
@@ -206,11 +829,24 @@ where
     }
 
     pub fn display(&self, data: &Matrix<T>) {
+        self.display_cells(|cell| {
+            let value = data[cell];
+            if !self.forbidden[cell] {
+                value.to_string()
+            } else if value == Default::default() {
+                "X".to_string()
+            } else {
+                format!("X ({})", value)
+            }
+        });
+    }
+
+    fn display_cells(&self, format: impl Fn((usize, usize)) -> String) {
         let mut table = Builder::default();
 
         let mut header = vec!["".to_string()];
         for j in 0..self.m {
-            header.push(format!("D{}", j + 1));
+            header.push(self.destination_label(j));
         }
         header.push("Supply".to_string());
         table.push_record(header);
@@ -218,10 +854,9 @@ where
         // Add the costs matrix
         for i in 0..self.n {
             let mut row = Vec::new();
-            row.push(format!("S{}", i + 1));
+            row.push(self.source_label(i));
             for j in 0..self.m {
-                let cost = data[(i, j)];
-                row.push(cost.to_string());
+                row.push(format((i, j)));
             }
             // Add the supply value
             row.push(self.supply[i].to_string());
@@ -241,26 +876,64 @@ where
                 .build()
                 .with(Style::rounded())
                 .with(Alignment::center())
-                .to_string()
         );
     }
 
     pub fn potentials<V>(&self, graph: &Graph<T>) -> (Vec<V>, Vec<V>)
     where
-        V: Default
-            + Clone
-            + Copy
-            + Add<Output = V>
-            + Sub<Output = V>
-            + Mul<Output = V>
-            + Div<Output = V>
-            + Ord
-            + SubAssign
-            + From<i8>
-            + Debug
-            + Neg<Output = V>,
+        V: Signed,
+        T: Into<V>,
+    {
+        // the basis is a spanning tree: starting from u_1 = 0, every edge S_i - D_j
+        // gives the potential on its other end through u_i - v_j = c_ij
+        let size = self.n + self.m;
+        let cells: Vec<(usize, usize)> = graph.edges.iter().filter_map(Self::cell_of).collect();
+        let big_m = self.big_m_for::<V>(cells.iter().copied());
+        let mut adjacency: Vec<Vec<(usize, usize)>> = vec![Vec::new(); size];
+        for &(i, j) in cells.iter() {
+            adjacency[i].push((i, j));
+            adjacency[self.n + j].push((i, j));
+        }
+
+        let mut u = vec![Default::default(); self.n];
+        let mut v = vec![Default::default(); self.m];
+        let mut visited = vec![false; size];
+        let mut queue = VecDeque::new();
+        visited[0] = true;
+        queue.push_back(0);
+        let mut reached = 1;
+
+        while let Some(node) = queue.pop_front() {
+            for &(i, j) in adjacency[node].iter() {
+                let next = if node < self.n { self.n + j } else { i };
+                if visited[next] {
+                    continue;
+                }
+                let cost = self.solver_cost((i, j), big_m);
+                if node < self.n {
+                    v[j] = u[i] - cost;
+                } else {
+                    u[i] = cost + v[j];
+                }
+                visited[next] = true;
+                reached += 1;
+                queue.push_back(next);
+            }
+        }
+
+        // a spanning tree reaches every node with exactly n + m - 1 edges
+        if reached != size || graph.edges.len() != size - 1 {
+            panic!("The graph is not a tree");
+        }
+        (u, v)
+    }
+
+    pub fn potentials_linear_system<V>(&self, graph: &Graph<T>) -> (Vec<V>, Vec<V>)
+    where
+        V: Signed,
         T: Into<V>,
     {
+        // cross-check of `potentials` through a dense (n+m)x(n+m) system, O((n+m)^3)
         let mut u = vec![Default::default(); self.n];
         let mut v = vec![Default::default(); self.m];
 
@@ -271,22 +944,16 @@ where
 
         let size = self.n + self.m;
 
+        let costs = self.solver_costs::<V>();
         let mut a: Matrix<i8> = Matrix::new_empty(size, size);
-        let mut b: Vec<T> = vec![Default::default(); size];
+        let mut b: Vec<V> = vec![Default::default(); size];
         // fill the matrix A and the vector B with the edges and the costs
         let mut l = 0;
         for edge in graph.edges.iter() {
-            if let (Some(i), Some(j)) = (
-                edge.from
-                    .strip_prefix("S")
-                    .and_then(|s| s.parse::<usize>().ok()),
-                edge.to
-                    .strip_prefix("D")
-                    .and_then(|s| s.parse::<usize>().ok()),
-            ) {
-                a[(l, i - 1)] = 1;
-                a[(l, self.n + j - 1)] = -1;
-                b[l] = self.costs[(i - 1, j - 1)];
+            if let Some((i, j)) = Self::cell_of(edge) {
+                a[(l, i)] = 1;
+                a[(l, self.n + j)] = -1;
+                b[l] = costs[(i, j)];
                 l += 1;
             }
         }
@@ -295,41 +962,1208 @@ where
         b[l] = 0.into();
 
         // solve the system of linear equations
-        let potentials = a.solve::<T, V>(&b);
+        let potentials = a.solve::<V, V>(&b);
 
         // fill the u and v vectors
-        for i in 0..self.n {
-            u[i] = potentials[i];
-        }
-        for j in 0..self.m {
-            v[j] = potentials[self.n + j];
-        }
+        u.copy_from_slice(&potentials[..self.n]);
+        v.copy_from_slice(&potentials[self.n..]);
         (u, v)
     }
 
     pub fn marginal_cost<V>(&self, graph: &Graph<T>) -> Matrix<V>
     where
-        V: Default
-            + Clone
-            + Copy
-            + Add<Output = V>
-            + Sub<Output = V>
-            + Mul<Output = V>
-            + Div<Output = V>
-            + Ord
-            + SubAssign
-            + From<i8>
-            + Debug
-            + Neg<Output = V>,
+        V: Signed,
         T: Into<V>,
     {
         let (u, v) = self.potentials::<V>(graph);
+        self.reduced_costs(&u, &v)
+    }
+
+    fn reduced_costs<V>(&self, u: &[V], v: &[V]) -> Matrix<V>
+    where
+        V: Signed,
+        T: Into<V>,
+    {
+        let costs = self.solver_costs::<V>();
         let mut marginal_cost = Matrix::new_empty(self.n, self.m);
         for i in 0..self.n {
             for j in 0..self.m {
-                marginal_cost[(i, j)] = self.costs[(i, j)].into() - (u[i] - v[j]);
+                marginal_cost[(i, j)] = costs[(i, j)] - (u[i] - v[j]);
             }
         }
         marginal_cost
     }
+
+    fn score<V>(&self, cell: (usize, usize), marginal_cost: V) -> V
+    where
+        V: Signed,
+        T: Into<V>,
+    {
+        // how much the objective gets worse per unit moved through a non-basic cell, away
+        // from the bound it sits at: negative when the cell should enter
+        let score = match self.objective {
+            Objective::Minimize => marginal_cost,
+            Objective::Maximize => -marginal_cost,
+        };
+        if self.at_capacity(cell) {
+            -score
+        } else {
+            score
+        }
+    }
+
+    pub fn cost_ranging<V>(&self, graph: &Graph<T>) -> Matrix<Interval<V>>
+    where
+        V: Signed,
+        T: Into<V>,
+    {
+        // the basis stays optimal while every non-basic cell has a non-negative score, its
+        // marginal cost seen the way `improve` prices it. the score of a cell follows its
+        // own cost up or down depending on the objective and on the bound it sits at
+        let (u, v) = self.potentials::<V>(graph);
+        let marginal_cost = self.reduced_costs(&u, &v);
+        let mut basic: Matrix<bool> = Matrix::new_empty(self.n, self.m);
+        for cell in graph.edges.iter().filter_map(Self::cell_of) {
+            basic[cell] = true;
+        }
+        // the cost of a cell that can never enter does not matter, its interval is unbounded
+        let priced =
+            |cell| !self.forbidden[cell] && self.capacity[cell] != Some(Default::default());
+
+        let mut ranges = Matrix::new_empty(self.n, self.m);
+        let mut scores = Vec::new();
+        for i in 0..self.n {
+            for j in 0..self.m {
+                if basic[(i, j)] || !priced((i, j)) {
+                    continue;
+                }
+                let score = self.score((i, j), marginal_cost[(i, j)]);
+                assert!(score >= V::default(), "The basis is not optimal");
+                let rises = (self.objective == Objective::Minimize) != self.at_capacity((i, j));
+                let cost: V = self.costs[(i, j)].into();
+                ranges[(i, j)] = if rises {
+                    Interval {
+                        lower: Some(cost - score),
+                        upper: None,
+                    }
+                } else {
+                    Interval {
+                        lower: None,
+                        upper: Some(cost + score),
+                    }
+                };
+                scores.push(((i, j), score, rises));
+            }
+        }
+
+        // changing the cost of a basic cell (i, j) by delta moves every potential on one side
+        // of the tree cut at (i, j): the marginal cost of a cell crossing the cut the same
+        // way, from the side of S_i to the other, goes down by delta and the one of a cell
+        // crossing it the other way goes up by delta
+        for (i, j) in graph.edges.iter().filter_map(Self::cell_of) {
+            if !priced((i, j)) {
+                continue;
+            }
+            let edge = Edge::new(
+                format!("S{}", i + 1),
+                format!("D{}", j + 1),
+                self.costs[(i, j)],
+            );
+            let mut sources = vec![false; self.n];
+            let mut destinations = vec![false; self.m];
+            for node in graph.component_without(&edge.from, &edge).iter() {
+                match node.split_at(1) {
+                    ("S", index) => sources[index.parse::<usize>().unwrap() - 1] = true,
+                    ("D", index) => destinations[index.parse::<usize>().unwrap() - 1] = true,
+                    _ => {}
+                }
+            }
+
+            let (mut lower, mut upper): (Option<V>, Option<V>) = (None, None);
+            for &((k, l), score, rises) in scores.iter() {
+                if sources[k] == destinations[l] {
+                    continue;
+                }
+                // the score of (k, l) is score + delta or score - delta
+                if rises != sources[k] {
+                    lower = Some(lower.map_or(-score, |lower| lower.max(-score)));
+                } else {
+                    upper = Some(upper.map_or(score, |upper| upper.min(score)));
+                }
+            }
+            let cost: V = self.costs[(i, j)].into();
+            ranges[(i, j)] = Interval {
+                lower: lower.map(|delta| cost + delta),
+                upper: upper.map(|delta| cost + delta),
+            };
+        }
+        ranges
+    }
+
+    pub fn shadow_prices<V>(&self, graph: &Graph<T>) -> (Vec<ShadowPrice<V>>, Vec<ShadowPrice<V>>)
+    where
+        V: Signed,
+        T: Into<V>,
+    {
+        // with u_1 = 0, one more unit at S_i and one less at S1 moves the objective by u_i,
+        // one more unit at D_j and at S1 moves it by -v_j
+        let (u, v) = self.potentials::<V>(graph);
+        // the prices of a basis that is not optimal mean nothing
+        let marginal_cost = self.reduced_costs(&u, &v);
+        let optimal = (0..self.n)
+            .flat_map(|i| (0..self.m).map(move |j| (i, j)))
+            .filter(|&cell| {
+                !self.forbidden[cell] && self.capacity[cell] != Some(Default::default())
+            })
+            .all(|cell| self.score(cell, marginal_cost[cell]) >= V::default());
+        assert!(optimal, "The basis is not optimal");
+        let sources = (0..self.n)
+            .map(|i| ShadowPrice {
+                price: u[i],
+                range: self.supply_range(graph, &format!("S{}", i + 1), self.supply[i]),
+            })
+            .collect();
+        let destinations = (0..self.m)
+            .map(|j| ShadowPrice {
+                price: -v[j],
+                range: self.supply_range(graph, &format!("D{}", j + 1), self.demand[j]),
+            })
+            .collect();
+        (sources, destinations)
+    }
+
+    fn supply_range<V>(&self, graph: &Graph<T>, node: &str, amount: T) -> Interval<V>
+    where
+        V: Signed,
+        T: Into<V>,
+    {
+        // the delta units go through the tree path between S1 and the node, from S1 to a
+        // destination and from a source to S1: along the path from S1, an edge S -> D
+        // carries delta more (less for a source) and an edge D -> S delta less (more for a
+        // source). the basis stays feasible while every basic cell keeps within its bounds,
+        // a forbidden cell being held at zero
+        let path = graph
+            .path("S1", node)
+            .expect("The basis is not a spanning tree");
+        // S1 balancing its own change leaves the plan as it is
+        if path.is_empty() {
+            return Interval::default();
+        }
+        let (mut lower, mut upper): (Option<V>, Option<V>) = (None, None);
+        let mut at = "S1";
+        for edge in path.iter() {
+            let forward = edge.from == at;
+            at = if forward { &edge.to } else { &edge.from };
+            let cell = Self::cell_of(edge).unwrap();
+            let flow: V = self.transport[cell].into();
+            let capacity: Option<V> = if self.forbidden[cell] {
+                Some(V::default())
+            } else {
+                self.capacity[cell].map(Into::into)
+            };
+            // room to push more on the cell and to take some off
+            let (up, down) = (capacity.map(|capacity| capacity - flow), flow);
+            let (up, down) = if forward == node.starts_with('D') {
+                (up, Some(down))
+            } else {
+                (Some(down), up)
+            };
+            if let Some(up) = up {
+                upper = Some(upper.map_or(up, |upper| upper.min(up)));
+            }
+            if let Some(down) = down {
+                lower = Some(lower.map_or(-down, |lower| lower.max(-down)));
+            }
+        }
+        let amount: V = amount.into();
+        Interval {
+            lower: lower.map(|delta| amount + delta),
+            upper: upper.map(|delta| amount + delta),
+        }
+    }
+
+    pub fn update_potentials<V>(
+        &self,
+        graph: &Graph<T>,
+        (u, v): &mut (Vec<V>, Vec<V>),
+        (i, j): (usize, usize),
+    ) where
+        V: Signed,
+        T: Into<V>,
+    {
+        // called once the entering cell (i, j) replaced the leaving one in `graph`, with the
+        // potentials of the previous basis: cutting the entering edge splits the tree in two
+        // and only the side without the root S1 has to move, by the marginal cost of the cell
+        let big_m = self.big_m_for(std::iter::once((i, j)));
+        let delta = self.solver_cost::<V>((i, j), big_m) - (u[i] - v[j]);
+        let entering = Edge::new(
+            format!("S{}", i + 1),
+            format!("D{}", j + 1),
+            self.costs[(i, j)],
+        );
+
+        let source_side = graph.component_without(&entering.from, &entering);
+        let (subtree, shift_up) = if source_side.contains("S1") {
+            // v_j' = u_i - c_ij = v_j - delta
+            (graph.component_without(&entering.to, &entering), false)
+        } else {
+            // u_i' = c_ij + v_j = u_i + delta
+            (source_side, true)
+        };
+
+        for node in subtree.iter() {
+            let potential = match node.split_at(1) {
+                ("S", index) => &mut u[index.parse::<usize>().unwrap() - 1],
+                ("D", index) => &mut v[index.parse::<usize>().unwrap() - 1],
+                _ => continue,
+            };
+            *potential = if shift_up {
+                *potential + delta
+            } else {
+                *potential - delta
+            };
+        }
+
+        debug_assert_eq!(
+            (u.clone(), v.clone()),
+            self.potentials::<V>(graph),
+            "Incremental potentials differ from a full recomputation"
+        );
+    }
+
+    pub fn optimize<V>(&mut self) -> Result<Solution<T>, SolveError>
+    where
+        V: Signed,
+        T: Into<V>,
+    {
+        // a table that was never initialized starts from the north-west corner, completing
+        // its empty basis would only give a plan shipping nothing
+        if self.basis.is_empty() {
+            self.north_west_corner();
+        }
+
+        // the heuristics ignore the capacities, a plan going over them is first brought
+        // back within bounds
+        let mut iterations = 0;
+        if self.exceeds_capacity() {
+            iterations += self.restore_capacity::<V>()?;
+        }
+
+        // the basis should already be a spanning tree, it is only completed
+        // when it was inferred from a degenerate transport plan
+        let mut graph = self.complete_basis();
+        iterations += self.improve::<V>(&mut graph);
+        self.solution(iterations)
+    }
+
+    pub fn is_assignment(&self) -> bool {
+        // n x n with every supply and demand at 1, the transportation simplex would be
+        // degenerate at almost every pivot
+        let one: T = 1.into();
+        self.n == self.m
+            && self.supply.iter().all(|&s| s == one)
+            && self.demand.iter().all(|&d| d == one)
+            && !self.is_capacitated()
+    }
+
+    pub fn assign<V>(&mut self) -> Result<Solution<T>, SolveError>
+    where
+        V: Signed,
+        T: Into<V>,
+    {
+        // the Hungarian algorithm on the solver costs, profits being negated and forbidden
+        // cells left at big-M; the n matched cells are completed into a basis
+        assert!(
+            self.is_assignment(),
+            "The table is not an assignment problem"
+        );
+        let costs = match self.objective {
+            Objective::Minimize => self.solver_costs::<V>(),
+            Objective::Maximize => self.solver_costs::<V>() * V::from(-1),
+        };
+        let assignment = hungarian::<V, V>(&costs);
+        self.transport = Matrix::new_empty(self.n, self.m);
+        for &cell in assignment.pairs.iter() {
+            self.transport[cell] = 1.into();
+        }
+        self.basis = assignment.pairs;
+        // the cells completing the basis are taken by cost, not by their marginal cost: the
+        // pivots bringing the basis to optimality are all degenerate and keep the plan
+        let mut graph = self.complete_basis();
+        let iterations = self.improve::<V>(&mut graph);
+        self.solution(iterations)
+    }
+
+    fn complete_basis(&mut self) -> Graph<T> {
+        let mut graph = self.get_graph();
+        let missing = (self.n + self.m - 1).saturating_sub(graph.edges.len());
+        if missing > 0 {
+            graph
+                .k_edge_augmentation(missing, self.get_unused_edges())
+                .expect("The transport plan cannot be completed into a basis");
+            self.basis = graph.edges.iter().filter_map(Self::cell_of).collect();
+        }
+        graph
+    }
+
+    pub fn to_network(&self) -> FlowNetwork<T> {
+        // one arc S_i -> D_j per allowed cell, priced like the heuristics see it
+        let costs = self.working_costs();
+        let mut network = FlowNetwork::new();
+        for i in 0..self.n {
+            network.add_node(format!("S{}", i + 1), self.supply[i], Default::default());
+        }
+        for j in 0..self.m {
+            network.add_node(format!("D{}", j + 1), Default::default(), self.demand[j]);
+        }
+        for i in 0..self.n {
+            for j in 0..self.m {
+                if !self.forbidden[(i, j)] {
+                    network.add_arc(
+                        format!("S{}", i + 1),
+                        format!("D{}", j + 1),
+                        costs[(i, j)],
+                        self.capacity[(i, j)],
+                    );
+                }
+            }
+        }
+        network
+    }
+
+    pub fn min_cost_flow<V>(&self) -> Result<Solution<T>, SolveError>
+    where
+        V: Signed,
+        T: Into<V>,
+    {
+        // solves the table as a network, leaving the table itself untouched
+        let network = self.to_network();
+        let flow = network.solve::<V>()?;
+        let mut transport = Matrix::new_empty(self.n, self.m);
+        for (edge, &amount) in network.graph().edges.iter().zip(flow.flows.iter()) {
+            let cell = Self::cell_of(edge).unwrap();
+            transport[cell] = amount;
+        }
+        Ok(Solution {
+            total_cost: self.cost_of(&transport),
+            transport,
+            iterations: flow.iterations,
+        })
+    }
+
+    pub fn network_simplex<V>(&self) -> Result<Solution<T>, SolveError>
+    where
+        V: Signed,
+        T: Into<V>,
+    {
+        // nodes 0..n are the sources and n..n+m the destinations, one arc per allowed cell;
+        // profits are maximized by minimizing their opposite
+        let mut supply = self.supply.clone();
+        supply.extend(vec![T::default(); self.m]);
+        let mut demand = vec![T::default(); self.n];
+        demand.extend(self.demand.iter().copied());
+        let mut network = NetworkSimplex::new(supply, demand);
+        let mut cells = Vec::new();
+        for i in 0..self.n {
+            for j in 0..self.m {
+                if self.forbidden[(i, j)] {
+                    continue;
+                }
+                let cost: V = match self.objective {
+                    Objective::Minimize => self.costs[(i, j)].into(),
+                    Objective::Maximize => -self.costs[(i, j)].into(),
+                };
+                network.add_arc(i, self.n + j, cost, self.capacity[(i, j)]);
+                cells.push((i, j));
+            }
+        }
+
+        let iterations = network.solve();
+        let unrouted = network.unrouted();
+        if !unrouted.is_empty() {
+            // sources left with supply and destinations left short, which do not mirror
+            // each other when the table is unbalanced
+            let nodes = unrouted
+                .into_iter()
+                .map(|node| {
+                    if node < self.n {
+                        format!("S{}", node + 1)
+                    } else {
+                        format!("D{}", node - self.n + 1)
+                    }
+                })
+                .collect();
+            return Err(SolveError::Unrouted { nodes });
+        }
+
+        let mut transport = Matrix::new_empty(self.n, self.m);
+        for (arc, &cell) in cells.iter().enumerate() {
+            transport[cell] = network.flow(arc);
+        }
+        Ok(Solution {
+            total_cost: self.cost_of(&transport),
+            transport,
+            iterations,
+        })
+    }
+
+    pub fn bottleneck<V>(&self, tie_break: bool) -> Result<Bottleneck<T>, SolveError>
+    where
+        V: Signed,
+        T: Into<V>,
+    {
+        // binary search on the distinct times for the smallest threshold leaving a feasible
+        // plan once every slower cell is forbidden; at that threshold the total time is
+        // minimized as a secondary objective when asked, any feasible plan is kept otherwise
+        let mut times: Vec<T> = (0..self.n)
+            .flat_map(|i| (0..self.m).map(move |j| (i, j)))
+            .filter(|&cell| !self.forbidden[cell])
+            .map(|cell| self.costs[cell])
+            .collect();
+        times.sort();
+        times.dedup();
+
+        // nothing helps a problem that is infeasible with every allowed cell open
+        let slowest = times.last().copied().unwrap_or_default();
+        let mut best = (slowest, self.within(slowest, false).network_simplex::<V>()?);
+        let (mut low, mut high) = (0, times.len().saturating_sub(1));
+        while low < high {
+            let middle = (low + high) / 2;
+            match self.within(times[middle], false).network_simplex::<V>() {
+                Ok(solution) => {
+                    best = (times[middle], solution);
+                    high = middle;
+                }
+                Err(_) => low = middle + 1,
+            }
+        }
+        let (time, solution) = best;
+        let solution = if tie_break {
+            self.within(time, true).network_simplex::<V>()?
+        } else {
+            solution
+        };
+
+        Ok(Bottleneck {
+            total_cost: self.cost_of(&solution.transport),
+            transport: solution.transport,
+            time,
+        })
+    }
+
+    fn within(&self, threshold: T, keep_costs: bool) -> Self {
+        // the same table with the cells slower than the threshold forbidden, and all costs
+        // at zero unless they are kept to rank the feasible plans
+        let mut table = self.clone();
+        table.objective = Objective::Minimize;
+        for i in 0..self.n {
+            for j in 0..self.m {
+                if self.costs[(i, j)] > threshold {
+                    table.forbidden[(i, j)] = true;
+                }
+                if !keep_costs {
+                    table.costs[(i, j)] = Default::default();
+                }
+            }
+        }
+        table
+    }
+
+    pub fn fixed_charge<V>(&mut self) -> Result<FixedCharge<T>, SolveError>
+    where
+        V: Signed,
+        T: Into<V>,
+    {
+        // local search over the neighbouring basic solutions: starting from the optimum of
+        // the variable costs, the pivot improving the sum of both costs the most is taken
+        // until none does, which ends at a local optimum since every move is strict
+        let solution = self.optimize::<V>()?;
+        let mut graph = self.get_graph();
+        let mut iterations = solution.iterations;
+        loop {
+            let mut best: Option<(V, (usize, usize), bool)> = None;
+            for i in 0..self.n {
+                for j in 0..self.m {
+                    if !self.can_enter((i, j)) || self.basis.contains(&(i, j)) {
+                        continue;
+                    }
+                    let increase = !self.at_capacity((i, j));
+                    let change = self.fixed_charge_change::<V>(&graph, (i, j), increase);
+                    if change.is_some_and(|change| best.is_none_or(|(min, _, _)| change < min)) {
+                        best = change.map(|change| (change, (i, j), increase));
+                    }
+                }
+            }
+            match best {
+                Some((change, cell, increase)) if change < V::default() => {
+                    self.pivot(&mut graph, cell, increase);
+                    iterations += 1;
+                }
+                _ => break,
+            }
+        }
+
+        Ok(FixedCharge {
+            transport: self.transport.clone(),
+            variable_cost: self.total_cost(),
+            fixed_cost: self.fixed_cost_of(&self.transport),
+            iterations,
+        })
+    }
+
+    fn fixed_charge_change<V>(
+        &self,
+        graph: &Graph<T>,
+        entering: (usize, usize),
+        increase: bool,
+    ) -> Option<V>
+    where
+        V: Signed,
+        T: Into<V>,
+    {
+        // change of the objective if the cell entered, None when the pivot moves no flow
+        // or would route flow through a forbidden cell
+        let (cycle, _, theta) = self.step(graph, entering, increase);
+        if theta == Default::default() {
+            return None;
+        }
+        let zero = T::default();
+        let mut variable = V::default();
+        let mut fixed = V::default();
+        for &(cell, sign) in cycle.iter() {
+            let flow = self.transport[cell];
+            let cost = self.costs[cell].into() * theta.into();
+            match sign {
+                Sign::Plus => {
+                    if self.forbidden[cell] {
+                        return None;
+                    }
+                    variable = variable + cost;
+                    if flow == zero {
+                        fixed = fixed + self.fixed[cell].into();
+                    }
+                }
+                Sign::Minus => {
+                    variable -= cost;
+                    if flow == theta {
+                        fixed -= self.fixed[cell].into();
+                    }
+                }
+            }
+        }
+        Some(match self.objective {
+            Objective::Minimize => variable + fixed,
+            // profits are earned and setup costs paid
+            Objective::Maximize => fixed - variable,
+        })
+    }
+
+    fn solution(&self, iterations: usize) -> Result<Solution<T>, SolveError> {
+        // with big-M costs, flow only stays on a forbidden cell when there is no other way
+        let cells: Vec<(usize, usize)> = (0..self.n)
+            .flat_map(|i| (0..self.m).map(move |j| (i, j)))
+            .filter(|&cell| self.forbidden[cell] && self.transport[cell] != Default::default())
+            .collect();
+        if !cells.is_empty() {
+            return Err(SolveError::Infeasible { cells });
+        }
+
+        Ok(Solution {
+            transport: self.transport.clone(),
+            total_cost: self.total_cost(),
+            iterations,
+        })
+    }
+
+    fn improve<V>(&mut self, graph: &mut Graph<T>) -> usize
+    where
+        V: Signed,
+        T: Into<V>,
+    {
+        let mut iterations = 0;
+        let mut potentials = self.potentials::<V>(graph);
+        loop {
+            // a cell at zero enters when its marginal cost is negative (positive when
+            // maximizing) and a cell at its capacity when the opposite holds, the cell
+            // improving the objective the most per unit is taken
+            let marginal_cost = self.reduced_costs(&potentials.0, &potentials.1);
+            let mut best: Option<(V, (usize, usize), bool)> = None;
+            for i in 0..self.n {
+                for j in 0..self.m {
+                    if !self.can_enter((i, j)) {
+                        continue;
+                    }
+                    let cost = self.score((i, j), marginal_cost[(i, j)]);
+                    let increase = !self.at_capacity((i, j));
+                    if cost < V::default() && best.is_none_or(|(min, _, _)| cost < min) {
+                        best = Some((cost, (i, j), increase));
+                    }
+                }
+            }
+            let (entering, increase) = match best {
+                Some((_, cell, increase)) => (cell, increase),
+                None => break,
+            };
+
+            if self.pivot(graph, entering, increase) {
+                self.update_potentials(graph, &mut potentials, entering);
+            }
+            iterations += 1;
+        }
+        iterations
+    }
+
+    fn restore_capacity<V>(&mut self) -> Result<usize, SolveError>
+    where
+        V: Signed,
+        T: Into<V>,
+    {
+        // an extra source S_{n+1} ships every demand and an extra destination D_{m+1}
+        // receives every supply, through forbidden cells: this plan is a basis within the
+        // capacities, and once optimized the flow still reaching D_{m+1} is supply that
+        // the capacities do not let through
+        let (n, m) = (self.n, self.m);
+        let total_supply = self.supply.iter().copied().sum::<T>();
+        let mut expanded = Self::new_empty(n + 1, m + 1);
+        expanded.objective = self.objective;
+        expanded.degeneracy = self.degeneracy;
+        for i in 0..n {
+            for j in 0..m {
+                expanded.costs[(i, j)] = self.costs[(i, j)];
+                expanded.forbidden[(i, j)] = self.forbidden[(i, j)];
+                expanded.capacity[(i, j)] = self.capacity[(i, j)];
+            }
+            expanded.supply[i] = self.supply[i];
+            expanded.forbidden[(i, m)] = true;
+            expanded.transport[(i, m)] = self.supply[i];
+            expanded.basis.push((i, m));
+        }
+        for j in 0..m {
+            expanded.demand[j] = self.demand[j];
+            expanded.forbidden[(n, j)] = true;
+            expanded.transport[(n, j)] = self.demand[j];
+            expanded.basis.push((n, j));
+        }
+        expanded.supply[n] = total_supply;
+        expanded.demand[m] = total_supply;
+        expanded.basis.push((n, m));
+
+        let mut graph = expanded.get_graph();
+        let iterations = expanded.improve::<V>(&mut graph);
+
+        let sources: Vec<usize> = (0..n)
+            .filter(|&i| expanded.transport[(i, m)] != Default::default())
+            .collect();
+        if !sources.is_empty() {
+            return Err(SolveError::OverCapacity { sources });
+        }
+
+        // the basic cells of the original table form a forest, completed by `optimize`
+        for i in 0..n {
+            for j in 0..m {
+                self.transport[(i, j)] = expanded.transport[(i, j)];
+            }
+        }
+        self.basis = expanded
+            .basis
+            .into_iter()
+            .filter(|&(i, j)| i < n && j < m)
+            .collect();
+        Ok(iterations)
+    }
+
+    pub fn cycle(&self, graph: &Graph<T>, (i, j): (usize, usize)) -> Option<Cycle> {
+        let entering = Edge::new(
+            format!("S{}", i + 1),
+            format!("D{}", j + 1),
+            self.costs[(i, j)],
+        );
+        let cycle = graph.stepping_stone(&entering)?;
+        cycle
+            .iter()
+            .map(|(edge, sign)| Self::cell_of(edge).map(|cell| (cell, *sign)))
+            .collect()
+    }
+
+    fn pivot(&mut self, graph: &mut Graph<T>, (i, j): (usize, usize), increase: bool) -> bool {
+        let (cycle, leaving, theta) = self.step(graph, (i, j), increase);
+        for &(cell, sign) in cycle.iter() {
+            match sign {
+                Sign::Plus => self.transport[cell] += theta,
+                Sign::Minus => self.transport[cell] -= theta,
+            }
+        }
+        if leaving == (i, j) {
+            return false;
+        }
+
+        graph.remove_edge(
+            &format!("S{}", leaving.0 + 1),
+            &format!("D{}", leaving.1 + 1),
+        );
+        graph.add_edge(
+            format!("S{}", i + 1),
+            format!("D{}", j + 1),
+            self.transport[(i, j)],
+        );
+
+        // the entering cell takes the place of the leaving one in the basis
+        let position = self.basis.iter().position(|&cell| cell == leaving).unwrap();
+        self.basis[position] = (i, j);
+        true
+    }
+
+    // cycle of the entering cell with its signs, leaving cell and flow shifted
+    fn step(
+        &self,
+        graph: &Graph<T>,
+        (i, j): (usize, usize),
+        increase: bool,
+    ) -> (Cycle, (usize, usize), T) {
+        let mut cycle = self
+            .cycle(graph, (i, j))
+            .expect("The basis is not a spanning tree");
+        if !increase {
+            // a cell leaving its capacity gives flow around the cycle instead of receiving it
+            for (_, sign) in cycle.iter_mut() {
+                *sign = match sign {
+                    Sign::Plus => Sign::Minus,
+                    Sign::Minus => Sign::Plus,
+                };
+            }
+        }
+
+        // the flow shifted around the cycle is the smallest room left, down to zero on the
+        // giving cells and up to the capacity on the receiving ones; the first basic cell
+        // to reach its bound leaves the basis, unless the entering cell reaches its other
+        // bound first, in which case the basis does not change
+        let (leaving, theta) = cycle
+            .iter()
+            .skip(1)
+            .chain(cycle.iter().take(1))
+            .filter_map(|&(cell, sign)| match sign {
+                Sign::Minus => Some((cell, self.transport[cell])),
+                Sign::Plus => self.capacity[cell].map(|cap| (cell, cap - self.transport[cell])),
+            })
+            .fold(
+                None,
+                |acc: Option<((usize, usize), T)>, (cell, room)| match acc {
+                    Some((_, min)) if min <= room => acc,
+                    _ => Some((cell, room)),
+                },
+            )
+            .expect("The cycle has no cell to give flow");
+        (cycle, leaving, theta)
+    }
+
+    fn cell_of(edge: &Edge<T>) -> Option<(usize, usize)> {
+        let (source, destination) = if edge.from.starts_with('S') {
+            (&edge.from, &edge.to)
+        } else {
+            (&edge.to, &edge.from)
+        };
+        let i = source.strip_prefix('S')?.parse::<usize>().ok()?;
+        let j = destination.strip_prefix('D')?.parse::<usize>().ok()?;
+        Some((i - 1, j - 1))
+    }
+
+    pub fn report_bottleneck(&self, bottleneck: &Bottleneck<T>) {
+        self.display(&bottleneck.transport);
+        println!("Longest time : {}", bottleneck.time);
+        println!("Total cost : {}", bottleneck.total_cost);
+    }
+
+    pub fn report_cost_ranging<V: Display>(&self, ranges: &Matrix<Interval<V>>) {
+        match self.objective {
+            Objective::Minimize => println!("Cost ranging :"),
+            Objective::Maximize => println!("Profit ranging :"),
+        }
+        self.display_cells(|cell| {
+            if self.forbidden[cell] {
+                "X".to_string()
+            } else {
+                ranges[cell].to_string()
+            }
+        });
+    }
+
+    pub fn report_shadow_prices<V: Display>(
+        &self,
+        (sources, destinations): &(Vec<ShadowPrice<V>>, Vec<ShadowPrice<V>>),
+    ) {
+        let mut table = Builder::default();
+        table.push_record(["", "Shadow price", "Amount", "Range"].map(String::from));
+        for (i, shadow) in sources.iter().enumerate() {
+            table.push_record([
+                self.source_label(i),
+                shadow.price.to_string(),
+                self.supply[i].to_string(),
+                if i == 0 {
+                    "-".to_string()
+                } else {
+                    shadow.range.to_string()
+                },
+            ]);
+        }
+        for (j, shadow) in destinations.iter().enumerate() {
+            table.push_record([
+                self.destination_label(j),
+                shadow.price.to_string(),
+                self.demand[j].to_string(),
+                shadow.range.to_string(),
+            ]);
+        }
+        println!(
+            "{}",
+            table
+                .build()
+                .with(Style::rounded())
+                .with(Alignment::center())
+        );
+        println!("Every change is balanced at S1");
+    }
+
+    pub fn report_fixed_charge(&self, plan: &FixedCharge<T>) {
+        self.display(&plan.transport);
+        println!("Variable cost : {}", plan.variable_cost);
+        println!("Fixed cost : {}", plan.fixed_cost);
+        match self.objective {
+            Objective::Minimize => {
+                println!("Total cost : {}", plan.variable_cost + plan.fixed_cost)
+            }
+            // the setup costs may exceed the profits
+            Objective::Maximize if plan.variable_cost < plan.fixed_cost => {
+                println!("Total profit : -{}", plan.fixed_cost - plan.variable_cost)
+            }
+            Objective::Maximize => {
+                println!("Total profit : {}", plan.variable_cost - plan.fixed_cost)
+            }
+        }
+        println!("Iterations : {}", plan.iterations);
+    }
+
+    pub fn report(&self, solution: &Solution<T>) {
+        self.display(&solution.transport);
+        match self.objective {
+            Objective::Minimize => println!("Total cost : {}", solution.total_cost),
+            Objective::Maximize => println!("Total profit : {}", solution.total_cost),
+        }
+        println!("Iterations : {}", solution.iterations);
+
+        // the saturated routes are the ones holding the solution back
+        for i in 0..self.n {
+            for j in 0..self.m {
+                let flow = solution.transport[(i, j)];
+                if flow != Default::default() && self.capacity[(i, j)] == Some(flow) {
+                    println!("Route S{}-D{} at capacity : {}", i + 1, j + 1, flow);
+                }
+            }
+        }
+
+        // the flows through the dummy line are not real shipments
+        match self.dummy {
+            Some(Dummy::Source) => {
+                for j in 0..self.m {
+                    let flow = solution.transport[(self.n - 1, j)];
+                    if flow != Default::default() {
+                        println!("Unmet demand at D{} : {}", j + 1, flow);
+                    }
+                }
+            }
+            Some(Dummy::Destination) => {
+                for i in 0..self.n {
+                    let flow = solution.transport[(i, self.m - 1)];
+                    if flow != Default::default() {
+                        println!("Surplus supply at S{} : {}", i + 1, flow);
+                    }
+                }
+            }
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restore_capacity_with_large_costs() {
+        // big-M for the cross cells of the expanded table is about 6e9, past u32
+        let costs = Matrix::new(vec![vec![1, 100000], vec![100000, 1]]);
+        let mut table: Table<u32> = Table::new(
+            costs,
+            Matrix::new_empty(2, 2),
+            vec![30000, 30000],
+            vec![30000, 30000],
+        );
+        table.set_capacity((0, 0), Some(20000));
+        table.least_cost();
+        assert_eq!(table.transport()[(0, 0)], 30000);
+
+        let solution = table.optimize::<i64>().unwrap();
+        assert_eq!(solution.transport[(0, 0)], 20000);
+        assert_eq!(solution.transport[(1, 1)], 20000);
+        assert_eq!(solution.total_cost, 2_000_040_000);
+    }
+
+    #[test]
+    fn empty_forbidden_cell_at_capacity() {
+        // column-minimum fills S2-D2 up to its capacity although the route is forbidden
+        let costs = Matrix::new(vec![
+            vec![19, 0, 11, 10],
+            vec![17, 0, 0, 1],
+            vec![15, 12, 1, 14],
+        ]);
+        let mut table: Table<u32> = Table::new(
+            costs,
+            Matrix::new_empty(3, 4),
+            vec![0, 5, 13],
+            vec![10, 5, 3, 0],
+        );
+        for cell in [(0, 1), (1, 1), (1, 2)] {
+            table.forbid(cell);
+        }
+        table.set_capacity((1, 1), Some(2));
+        table.set_capacity((0, 3), Some(0));
+
+        let simplex = table.network_simplex::<i64>().unwrap();
+        table.column_minimum();
+        let solution = table.optimize::<i64>().unwrap();
+        assert_eq!(solution.transport[(1, 1)], 0);
+        assert_eq!(solution.total_cost, simplex.total_cost);
+        assert_eq!(solution.total_cost, 223);
+    }
+
+    #[test]
+    fn fixed_charge_keeps_the_plan_feasible() {
+        // the cheapest route per unit is expensive to open, so the crossed plan wins
+        let costs = Matrix::new(vec![vec![1, 4], vec![5, 1]]);
+        let mut table: Table<u32> =
+            Table::new(costs, Matrix::new_empty(2, 2), vec![4, 4], vec![4, 4]);
+        table.set_fixed_costs(Matrix::new(vec![vec![100, 0], vec![0, 0]]));
+
+        let mut start = table.clone();
+        start.least_cost();
+        let optimum = start.optimize::<i64>().unwrap();
+        let start_total = optimum.total_cost + start.fixed_cost_of(&optimum.transport);
+
+        table.least_cost();
+        let plan = table.fixed_charge::<i64>().unwrap();
+        let transport = &plan.transport;
+        table.assert_ships_everything(transport, "fixed charge");
+        assert_eq!(plan.variable_cost, table.cost_of(transport));
+        assert_eq!(plan.fixed_cost, table.fixed_cost_of(transport));
+        assert!(plan.variable_cost + plan.fixed_cost <= start_total);
+        assert_eq!(transport[(0, 0)], 0);
+    }
+
+    #[test]
+    fn heuristics_avoid_forbidden_routes() {
+        // north-west-corner and column-minimum alone both send 9 units through S3-D5
+        for initializer in Initializer::ALL {
+            let mut table: Table<u32> = Table::from_file("data/13.txt").unwrap();
+            table.initialize(initializer);
+            for i in 0..3 {
+                for j in 0..6 {
+                    let flow = table.transport()[(i, j)];
+                    assert!(
+                        !table.is_forbidden((i, j)) || flow == 0,
+                        "{:?}",
+                        initializer
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn optimize_without_initial_solution() {
+        let mut table: Table<u32> = Table::from_file("data/1.txt").unwrap();
+        let solution = table.optimize::<i64>().unwrap();
+        table.assert_ships_everything(&solution.transport, "data/1.txt");
+
+        table.initialize(Initializer::Russell);
+        assert_eq!(
+            solution.total_cost,
+            table.optimize::<i64>().unwrap().total_cost
+        );
+    }
+
+    #[test]
+    fn network_simplex_reports_short_destinations() {
+        // less supply than demand, nothing is left at the source
+        let costs = Matrix::new(vec![vec![2, 3]]);
+        let table: Table<u32> = Table::new(costs, Matrix::new_empty(1, 2), vec![4], vec![3, 3]);
+        let error = table.network_simplex::<i64>().err().unwrap();
+        assert_eq!(
+            error,
+            SolveError::Unrouted {
+                nodes: vec!["D2".to_string()]
+            }
+        );
+    }
+
+    #[test]
+    fn parse_errors_are_located() {
+        let cases = [
+            (
+                "2 2\n30 20 100\n10 50\n100 100\n",
+                3,
+                3,
+                ParseErrorKind::MissingToken("supply"),
+            ),
+            (
+                "1 1\n5 3 9\n3\n",
+                2,
+                3,
+                ParseErrorKind::TrailingData("9".to_string()),
+            ),
+            (
+                "1 1\n5 a\n3\n",
+                2,
+                2,
+                ParseErrorKind::NotANumber("a".to_string()),
+            ),
+            (
+                "1 1\n5 3\n4\n",
+                3,
+                0,
+                ParseErrorKind::Unbalanced {
+                    supply: "3".to_string(),
+                    demand: "4".to_string(),
+                },
+            ),
+            (
+                "3 1\n5 3\n3\n",
+                4,
+                0,
+                ParseErrorKind::WrongDimensions {
+                    expected: 5,
+                    found: 3,
+                },
+            ),
+        ];
+        for (k, (content, line, column, kind)) in cases.into_iter().enumerate() {
+            let name = format!("transporteur-{}-{}.txt", std::process::id(), k);
+            let path = std::env::temp_dir().join(name);
+            std::fs::write(&path, content).unwrap();
+            let file = path.to_string_lossy();
+            let error = Table::<u32>::from_file(&file).err().unwrap();
+            std::fs::remove_file(&path).unwrap();
+            assert_eq!(error, ParseError::new(&file, line, column, kind));
+        }
+
+        let error = Table::<u32>::from_file("data/missing.txt").err().unwrap();
+        assert!(matches!(error.kind, ParseErrorKind::Io(_)));
+        assert_eq!((error.line, error.column), (0, 0));
+    }
+
+    #[test]
+    fn degeneracy_is_resolved_the_same_way_every_run() {
+        // every cost ties and the diagonal plan leaves two cells of the basis to pick
+        let solve = |degeneracy: Degeneracy| {
+            let transport = Matrix::new(vec![vec![1, 0, 0], vec![0, 1, 0], vec![0, 0, 1]]);
+            let mut table: Table<u32> =
+                Table::new(Matrix::new_empty(3, 3), transport, vec![1; 3], vec![1; 3]);
+            table.set_degeneracy(degeneracy);
+            table.optimize::<i64>().unwrap();
+            table.basis().clone()
+        };
+        for degeneracy in [
+            Degeneracy::CheapestCost,
+            Degeneracy::Lexicographic,
+            Degeneracy::Seeded(7),
+        ] {
+            let basis = solve(degeneracy);
+            assert_eq!(basis.len(), 5, "{:?}", degeneracy);
+            for _ in 0..10 {
+                assert_eq!(solve(degeneracy), basis, "{:?}", degeneracy);
+            }
+        }
+        assert_eq!(
+            solve(Degeneracy::Lexicographic),
+            vec![(0, 0), (1, 1), (2, 2), (0, 1), (0, 2)]
+        );
+    }
+
+    #[test]
+    fn assignment_agrees_with_modi() {
+        let costs = Matrix::new(vec![
+            vec![9, 2, 7, 8],
+            vec![6, 4, 3, 7],
+            vec![5, 8, 1, 8],
+            vec![7, 6, 9, 4],
+        ]);
+        for objective in [Objective::Minimize, Objective::Maximize] {
+            let mut table: Table<u32> = Table::new(
+                costs.clone(),
+                Matrix::new_empty(4, 4),
+                vec![1; 4],
+                vec![1; 4],
+            );
+            table.set_objective(objective);
+            table.north_west_corner();
+            let modi = table.optimize::<i64>().unwrap();
+            table.north_west_corner();
+            let hungarian = table.assign::<i64>().unwrap();
+            assert_eq!(hungarian.total_cost, modi.total_cost, "{:?}", objective);
+            assert_eq!(table.basis().len(), 7, "{:?}", objective);
+        }
+    }
+
+    #[test]
+    fn bottleneck_avoids_the_slowest_route() {
+        // S1 has to reach D2 in 6, S2-D2 at 9 is only needed when S1 fills D1
+        let times = Matrix::new(vec![vec![1, 6], vec![2, 9]]);
+        let table: Table<u32> = Table::new(times, Matrix::new_empty(2, 2), vec![3, 1], vec![2, 2]);
+        let plan = table.bottleneck::<i64>(false).unwrap();
+        assert_eq!(plan.time, 6);
+        assert_eq!(plan.transport[(1, 1)], 0);
+
+        // both plans take 5 at most, the total time only separates them on a tie-break
+        let times = Matrix::new(vec![vec![5, 5], vec![5, 1]]);
+        let table: Table<u32> = Table::new(times, Matrix::new_empty(2, 2), vec![1; 2], vec![1; 2]);
+        let plan = table.bottleneck::<i64>(true).unwrap();
+        assert_eq!(plan.time, 5);
+        assert_eq!(plan.total_cost, 6);
+    }
+
+    #[test]
+    fn shadow_prices_after_assignment() {
+        let costs = Matrix::new(vec![vec![1, 9, 9], vec![9, 9, 1], vec![9, 1, 3]]);
+        let mut table: Table<u32> =
+            Table::new(costs, Matrix::new_empty(3, 3), vec![1; 3], vec![1; 3]);
+        table.north_west_corner();
+        table.assign::<i64>().unwrap();
+
+        // moving the unit of S2 to S1 sends it to D3 at 9 instead of 1
+        let (sources, _) = table.shadow_prices::<i64>(&table.get_graph());
+        assert_eq!(sources[1].price, -8);
+        assert_eq!(sources[1].range.lower, Some(0));
+    }
+
+    #[test]
+    fn cost_ranging_after_assignment() {
+        let costs = Matrix::new(vec![vec![1, 9, 9], vec![9, 9, 1], vec![9, 1, 3]]);
+        let mut table: Table<u32> = Table::new(
+            costs.clone(),
+            Matrix::new_empty(3, 3),
+            vec![1; 3],
+            vec![1; 3],
+        );
+        table.north_west_corner();
+        assert_eq!(table.assign::<i64>().unwrap().total_cost, 3);
+
+        // every cost lies within its own interval
+        let ranges = table.cost_ranging::<i64>(&table.get_graph());
+        for i in 0..3 {
+            for j in 0..3 {
+                let cost = costs[(i, j)] as i64;
+                let range = ranges[(i, j)];
+                assert!(range.lower.is_none_or(|lower| lower <= cost), "{}", range);
+                assert!(range.upper.is_none_or(|upper| cost <= upper), "{}", range);
+            }
+        }
+    }
 }
@@ -0,0 +1,188 @@
+// transshipment problem: every node of the network may ship, receive and relay goods
+// along directed arcs, solved as a transportation table where each node is both a
+// source and a destination with a buffer stock large enough to relay all the supply
+
+use crate::tools::error::SolveError;
+use crate::tools::graph::Edge;
+use crate::tools::initial::Initializer;
+use crate::tools::matrix::Matrix;
+use crate::tools::number::{Number, Signed};
+use crate::tools::table::{Dummy, Table};
+
+pub struct Transshipment<T> {
+    nodes: Vec<String>,
+    supply: Vec<T>,
+    demand: Vec<T>,
+    // directed arcs, the weight is the cost of one unit from `from` to `to`
+    arcs: Vec<Edge<T>>,
+}
+
+pub struct TransshipmentSolution<T> {
+    // the arcs carrying flow, the weight is the flow
+    pub flows: Vec<Edge<T>>,
+    // demand left unmet and supply left unshipped at each node, when the network is unbalanced
+    pub unmet: Vec<(String, T)>,
+    pub surplus: Vec<(String, T)>,
+    pub total_cost: T,
+    pub iterations: usize,
+}
+
+impl<T> Transshipment<T>
+where
+    T: Number,
+{
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            supply: Vec::new(),
+            demand: Vec::new(),
+            arcs: Vec::new(),
+        }
+    }
+
+    pub fn add_node(&mut self, name: &str, supply: T, demand: T) {
+        if self.nodes.iter().any(|node| node == name) {
+            panic!("Node already exists");
+        }
+        self.nodes.push(name.to_string());
+        self.supply.push(supply);
+        self.demand.push(demand);
+    }
+
+    pub fn add_arc(&mut self, from: &str, to: &str, cost: T) {
+        // arcs are directed, unlike the edges of a Graph
+        if self.index_of(from).is_none() || self.index_of(to).is_none() {
+            panic!("Unknown node");
+        }
+        if self.arcs.iter().any(|arc| arc.from == from && arc.to == to) {
+            panic!("Arc already exists");
+        }
+        self.arcs
+            .push(Edge::new(from.to_string(), to.to_string(), cost));
+    }
+
+    pub fn nodes(&self) -> &Vec<String> {
+        &self.nodes
+    }
+
+    pub fn arcs(&self) -> &Vec<Edge<T>> {
+        &self.arcs
+    }
+
+    fn index_of(&self, name: &str) -> Option<usize> {
+        self.nodes.iter().position(|node| node == name)
+    }
+
+    pub fn to_table(&self) -> Table<T> {
+        // row k ships s_k + B and column k receives d_k + B, where the buffer B is the total
+        // supply: the flow left on the diagonal cell (k, k) is the part of the buffer that
+        // does not go through k, every pair of nodes without an arc is forbidden
+        let size = self.nodes.len();
+        let buffer = self.supply.iter().copied().sum::<T>();
+        let mut costs = Matrix::new_empty(size, size);
+        for arc in self.arcs.iter() {
+            let from = self.index_of(&arc.from).unwrap();
+            let to = self.index_of(&arc.to).unwrap();
+            costs[(from, to)] = arc.weight;
+        }
+        let supply = self.supply.iter().map(|&s| s + buffer).collect();
+        let demand = self.demand.iter().map(|&d| d + buffer).collect();
+
+        let mut table = Table::new(costs, Matrix::new_empty(size, size), supply, demand);
+        for from in 0..size {
+            for to in 0..size {
+                let linked = from == to
+                    || self
+                        .arcs
+                        .iter()
+                        .any(|arc| arc.from == self.nodes[from] && arc.to == self.nodes[to]);
+                if !linked {
+                    table.forbid((from, to));
+                }
+            }
+        }
+        // a network whose supply and demand differ gets a dummy line at no cost
+        table.balance(Default::default());
+        table
+    }
+
+    pub fn solve<V>(&self, initializer: Initializer) -> Result<TransshipmentSolution<T>, SolveError>
+    where
+        V: Signed,
+        T: Into<V>,
+    {
+        let mut table = self.to_table();
+        table.initialize(initializer);
+        let solution = table.optimize::<V>()?;
+
+        // only the cells of real arcs are shipments, the diagonal and the dummy line are not
+        let flows = self
+            .arcs
+            .iter()
+            .filter_map(|arc| {
+                let from = self.index_of(&arc.from).unwrap();
+                let to = self.index_of(&arc.to).unwrap();
+                let flow = solution.transport[(from, to)];
+                (flow != Default::default())
+                    .then(|| Edge::new(arc.from.clone(), arc.to.clone(), flow))
+            })
+            .collect();
+
+        // a dummy source feeds the demand that cannot be met, a dummy destination takes the
+        // supply that is not needed
+        let size = self.nodes.len();
+        let (mut unmet, mut surplus) = (Vec::new(), Vec::new());
+        for k in 0..size {
+            let (lost, flow) = match table.dummy() {
+                Some(Dummy::Source) => (&mut unmet, solution.transport[(size, k)]),
+                Some(Dummy::Destination) => (&mut surplus, solution.transport[(k, size)]),
+                None => break,
+            };
+            if flow != Default::default() {
+                lost.push((self.nodes[k].clone(), flow));
+            }
+        }
+
+        Ok(TransshipmentSolution {
+            flows,
+            unmet,
+            surplus,
+            total_cost: solution.total_cost,
+            iterations: solution.iterations,
+        })
+    }
+
+    pub fn report(&self, solution: &TransshipmentSolution<T>) {
+        for flow in solution.flows.iter() {
+            println!("{} -> {} : {}", flow.from, flow.to, flow.weight);
+        }
+        for (node, flow) in solution.unmet.iter() {
+            println!("Unmet demand at {} : {}", node, flow);
+        }
+        for (node, flow) in solution.surplus.iter() {
+            println!("Surplus supply at {} : {}", node, flow);
+        }
+        println!("Total cost : {}", solution.total_cost);
+        println!("Iterations : {}", solution.iterations);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unmet_demand_is_reported() {
+        let mut network: Transshipment<u32> = Transshipment::new();
+        network.add_node("A", 5, 0);
+        network.add_node("W", 0, 0);
+        network.add_node("B", 0, 9);
+        network.add_arc("A", "W", 1);
+        network.add_arc("W", "B", 1);
+
+        let solution = network.solve::<i64>(Initializer::NorthWestCorner).unwrap();
+        assert_eq!(solution.total_cost, 10);
+        assert_eq!(solution.unmet, vec![("B".to_string(), 4)]);
+        assert!(solution.surplus.is_empty());
+    }
+}